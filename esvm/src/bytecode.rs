@@ -42,18 +42,22 @@ pub enum Instr {
     IExtCodeCopy,
     IRDataSize,
     IRDataCopy,
+    IExtCodeHash,
     IBlockHash,
     ICoinBase,
     ITimeStamp,
     INumber,
-    IDifficulty,
+    IPrevRandao,
     IGasLimit,
     IPop,
     IMLoad,
     IMStore,
     IMStore8,
+    IMCopy,
     ISLoad,
     ISStore,
+    ITLoad,
+    ITStore,
     IJump,
     IJumpIf,
     IPC,
@@ -75,6 +79,10 @@ pub enum Instr {
     ISelfDestruct,
     IInvalid,
     IChainId,
+    ISelfBalance,
+    IBaseFee,
+    IBlobHash,
+    IBlobBaseFee,
 }
 
 impl Instr {
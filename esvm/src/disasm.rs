@@ -177,18 +177,19 @@ impl Disasm {
             0x3c => Instr::IExtCodeCopy,
             0x3d => Instr::IRDataSize,
             0x3e => Instr::IRDataCopy,
+            0x3f => Instr::IExtCodeHash,
             //...
             0x40 => Instr::IBlockHash,
             0x41 => Instr::ICoinBase,
             0x42 => Instr::ITimeStamp,
             0x43 => Instr::INumber,
-            0x44 => Instr::IDifficulty,
+            0x44 => Instr::IPrevRandao,
             0x45 => Instr::IGasLimit,
             0x46 => Instr::IChainId,
-            // 0x47 => Instr::ISelfBalance, // TODO
-            // 0x48 => Instr::IBaseFee, // TODO
-            // 0x49 => BLOBHASH // TODO
-            // 0x4A => BLOBBASEFEE // TODO
+            0x47 => Instr::ISelfBalance,
+            0x48 => Instr::IBaseFee,
+            0x49 => Instr::IBlobHash,
+            0x4a => Instr::IBlobBaseFee,
             //...
             0x50 => Instr::IPop,
             0x51 => Instr::IMLoad,
@@ -202,9 +203,9 @@ impl Disasm {
             0x59 => Instr::IMSize,
             0x5a => Instr::IGas,
             0x5b => Instr::IJumpDest,
-            // 0x5C => TLOAD // TODO
-            // 0x5D => TSTORE // TODO
-            // 0x5E => MEMCOPY // TODO
+            0x5c => Instr::ITLoad,
+            0x5d => Instr::ITStore,
+            0x5e => Instr::IMCopy,
             0x5f => {
                 // Only cares about how many bytes to read from the bytecode, which is 0 and not the actual value 0
                 Instr::IPush(vec![0; 0])
@@ -46,14 +46,16 @@ use time::PreciseTime;
 use crate::se::{
     env::{AccountId, GLOBAL_COVERAGE_MAP},
     expr::{bval::BitVec, formel_builder::KECCAK_STATS},
-    symbolic_analysis::{Analysis, AnalysisMode},
+    symbolic_analysis::{Analysis, AnalysisMode, TxData},
 };
 
 pub use crate::se::{
     env::{self, Env, SeEnviroment},
     expr::solver::{create_pool, SolverPool, Solvers},
-    symbolic_analysis::{Attack, AttackType, ExplorationResult, SeConfig, CONFIG},
-    symbolic_state::{Flags, ResultState},
+    symbolic_analysis::{
+        Attack, AttackType, ExplorationResult, PrivilegedEffect, SeConfig, CONFIG,
+    },
+    symbolic_state::{Flags, ResultState, RevertReason},
 };
 
 use crate::bytecode::Instr;
@@ -79,10 +81,14 @@ pub struct AnalysisResult {
 
     pub executed: bool,
     pub copy_instructions: bool,
+    pub unsupported_exponentiation: bool,
+    pub unsupported_probes: bool,
 
     pub attacks: Option<Vec<Attack>>,
     pub precompiled_contracts: Option<Vec<PrecompiledContracts>>,
     pub loaded_accounts: Option<Vec<LoadedAccount>>,
+    pub emitted_events: Option<Vec<EmittedEvent>>,
+    pub privileged_effects: Option<Vec<PrivilegedEffect>>,
     pub analysis_time: Option<Duration>,
 }
 
@@ -205,9 +211,13 @@ pub fn symbolic_analysis(se_env: SeEnviroment, config: SeConfig, pool: Solvers)
         code_length: 0,
         executed: false,
         copy_instructions: false,
+        unsupported_exponentiation: false,
+        unsupported_probes: false,
         precompiled_contracts: None,
         attacks: None,
         loaded_accounts: None,
+        emitted_events: None,
+        privileged_effects: None,
         analysis_time: None,
     };
     let start = PreciseTime::now();
@@ -234,7 +244,17 @@ pub fn symbolic_analysis(se_env: SeEnviroment, config: SeConfig, pool: Solvers)
     info!("Starting first round.");
     info!("=========================================================");
     analysis.symbolic_round();
+    // later rounds reuse the inferred token balance mappings
+    let mut config = config;
+    config.balance_slots = analysis.balance_slots().to_vec();
     let exp_res = analysis.exploration_result();
+    analysis_result.privileged_effects = exp_res.privileged_effects.clone();
+    update_round_statistics(&mut analysis_result, &exp_res);
+
+    // locked ether is a property of the whole exploration
+    let mut deposit = exp_res.deposit.clone();
+    let mut withdrawal = exp_res.withdrawal;
+    let mut explored_paths = exp_res.explored_paths;
 
     if exp_res.found_attacks() {
         if cfg!(feature = "stats") {
@@ -242,8 +262,12 @@ pub fn symbolic_analysis(se_env: SeEnviroment, config: SeConfig, pool: Solvers)
             info!("Stats at the end of the analysis: {:?}", *handle);
         }
 
+        analysis_result = update_analysis_result(analysis_result, exp_res);
+        if config.locked_ether && !withdrawal {
+            report_locked_ether(&mut analysis_result, deposit, 1, explored_paths);
+        }
         analysis_result.analysis_time = Some(start.to(PreciseTime::now()).to_std().unwrap());
-        return update_analysis_result(analysis_result, exp_res);
+        return analysis_result;
     }
 
     let mut states;
@@ -287,7 +311,13 @@ pub fn symbolic_analysis(se_env: SeEnviroment, config: SeConfig, pool: Solvers)
 
         let mut new_states = Vec::new();
         for ana in anas.into_inner().unwrap() {
-            let exp_res = ana.exploration_result();
+            let mut exp_res = ana.exploration_result();
+            update_round_statistics(&mut analysis_result, &exp_res);
+            withdrawal |= exp_res.withdrawal;
+            explored_paths += exp_res.explored_paths;
+            if deposit.is_none() {
+                deposit = exp_res.deposit.take();
+            }
             if exp_res.found_attacks() {
                 results.lock().unwrap().push(exp_res);
             } else {
@@ -322,6 +352,10 @@ pub fn symbolic_analysis(se_env: SeEnviroment, config: SeConfig, pool: Solvers)
             }
         }
 
+        if config.locked_ether && !withdrawal {
+            report_locked_ether(&mut analysis_result, deposit, counter - 1, explored_paths);
+        }
+
         analysis_result.analysis_time = Some(start.to(PreciseTime::now()).to_std().unwrap());
         return analysis_result;
     }
@@ -330,6 +364,9 @@ pub fn symbolic_analysis(se_env: SeEnviroment, config: SeConfig, pool: Solvers)
     for res in results {
         analysis_result = update_analysis_result(analysis_result, res);
     }
+    if config.locked_ether && !withdrawal {
+        report_locked_ether(&mut analysis_result, deposit, counter - 1, explored_paths);
+    }
 
     analysis_result.analysis_time = Some(start.to(PreciseTime::now()).to_std().unwrap());
     analysis_result
@@ -340,6 +377,14 @@ pub fn arguments<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
         .arg(Arg::with_name("block").long("block").takes_value(true).help("The blocknumber to evaluate, defaults to latest block. Note you will need an archive node for anaylzing blocks which are not the latest."))
         .arg(Arg::with_name("loop_bound").long("loop-bound").short("b").takes_value(true).help("Set bound for loops"))
         .arg(Arg::with_name("call_bound").long("call-bound").short("c").takes_value(true).help("Set bound for calls"))
+        .arg(Arg::with_name("exp_bound").long("exp-bound").takes_value(true).help("Set bound for case splitting on symbolic exponents"))
+        .arg(Arg::with_name("blob_count").long("blob-count").takes_value(true).help("Set the number of blobs a transaction may carry, at most 6. Defaults to 1."))
+        .arg(Arg::with_name("panic_codes").long("panic-codes").takes_value(true).help("Comma separated list of Solidity panic codes reported as violations, e.g. 0x01,0x11. Defaults to assert failures (0x01) only."))
+        .arg(Arg::with_name("forbidden_errors").long("forbidden-errors").takes_value(true).help("Comma separated list of custom error selectors (e.g. 0x1234abcd) or require messages which must never be reachable"))
+        .arg(Arg::with_name("forbidden_events").long("forbidden-events").takes_value(true).help("Comma separated list of event signatures, i.e. first topics, which the victim must never emit"))
+        .arg(Arg::with_name("critical_slots").long("critical-slots").takes_value(true).help("Comma separated list of storage slots, e.g. 0x0,0x1, which must not be overwritable through an attacker controlled slot"))
+        .arg(Arg::with_name("balance_slots").long("balance-slots").takes_value(true).help("Comma separated list of storage slots of token balance mappings, e.g. 0x0, checked for balance increases of the attacker. Defaults to the mappings read by the victim's balanceOf and transfer functions."))
+        .arg(Arg::with_name("dos_selectors").long("dos-selectors").takes_value(true).help("Comma separated list of function selectors, e.g. 0x1234abcd, which must stay callable by honest accounts after attacker transactions"))
         .arg(Arg::with_name("message_bound").long("message-bound").short("m").takes_value(true).help("Set bound for message iteration"))
         .arg(Arg::with_name("solver-timeout").long("solver-timeout").takes_value(true).help("Set solver timeout in milliseconds"))
         .arg(Arg::with_name("cores").long("cores").takes_value(true).help("Set the amount of cores the se can use"))
@@ -349,6 +394,11 @@ pub fn arguments<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
         // Analysis
         .arg(Arg::with_name("debug_graph").short("d").long("debug-grap").help("Dump debug graph after analysis"))
         .arg(Arg::with_name("no_verify").long("no-verify").help("Skip verification phase."))
+        .arg(Arg::with_name("overflow_check").long("overflow").help("Report unchecked arithmetic which wraps around before being stored or sent."))
+        .arg(Arg::with_name("locked_ether").long("locked-ether").help("Report contracts which can receive ether, but never transfer it out or self-destruct within the bounds."))
+        .arg(Arg::with_name("initializer").long("initializer").help("Additionally explore the first transaction on the victim with zeroed storage, as if freshly deployed, and report those which set its owner, admin or implementation slots to the attacker, e.g. an unprotected initializer. All other checks keep the victim's real storage."))
+        .arg(Arg::with_name("privilege_report").long("privilege-report").help("Report the state changing operations reachable through each function selector and the caller checks guarding them."))
+        .arg(Arg::with_name("tx_origin").long("tx-origin").help("Additionally explore attacker transactions relayed through an attacker controlled contract, so the transaction origin can be a phished privileged account."))
         .arg(Arg::with_name("symbolic_storage").long("symbolic-storage").help("Use symbolic storage mode."))
         .arg(Arg::with_name("dump-solver").long("dump-solver").help("Dump all solver queries to ./queries"))
 }
@@ -370,6 +420,60 @@ pub fn set_global_config(matches: &clap::ArgMatches) {
     if let Some(b) = matches.value_of("message_bound") {
         config.message_bound = b.parse().expect("Incorrect bound parameter supplied!");
     }
+    if let Some(b) = matches.value_of("exp_bound") {
+        config.exp_bound = b.parse().expect("Incorrect bound parameter supplied!");
+    }
+    if let Some(b) = matches.value_of("blob_count") {
+        config.blob_count = b.parse().expect("Incorrect blob count supplied!");
+    }
+    if let Some(codes) = matches.value_of("panic_codes") {
+        config.panic_codes = codes
+            .split(',')
+            .map(|c| {
+                usize::from_str_radix(c.trim().trim_start_matches("0x"), 16)
+                    .expect("Incorrect panic code supplied!")
+            })
+            .collect();
+    }
+    if let Some(errors) = matches.value_of("forbidden_errors") {
+        config.forbidden_reverts = errors
+            .split(',')
+            .map(|e| {
+                let e = e.trim();
+                match e.strip_prefix("0x").map(|sel| u32::from_str_radix(sel, 16)) {
+                    Some(Ok(selector)) if e.len() == 10 => RevertReason::Custom(selector),
+                    _ => RevertReason::Error(String::from(e)),
+                }
+            })
+            .collect();
+    }
+    if let Some(events) = matches.value_of("forbidden_events") {
+        config.forbidden_events = events
+            .split(',')
+            .map(|sig| sig.trim().parse::<U256>().expect("Incorrect event signature supplied!"))
+            .collect();
+    }
+    if let Some(slots) = matches.value_of("critical_slots") {
+        config.critical_slots = slots
+            .split(',')
+            .map(|slot| slot.trim().parse::<U256>().expect("Incorrect storage slot supplied!"))
+            .collect();
+    }
+    if let Some(slots) = matches.value_of("balance_slots") {
+        config.balance_slots = slots
+            .split(',')
+            .map(|slot| slot.trim().parse::<U256>().expect("Incorrect storage slot supplied!"))
+            .collect();
+    }
+    if let Some(selectors) = matches.value_of("dos_selectors") {
+        config.dos_selectors = selectors
+            .split(',')
+            .map(|sel| {
+                u32::from_str_radix(sel.trim().trim_start_matches("0x"), 16)
+                    .expect("Incorrect function selector supplied!")
+            })
+            .collect();
+    }
     if let Some(b) = matches.value_of("cores") {
         config.cores = b.parse().expect("Incorrect bound parameter supplied!");
     }
@@ -384,6 +488,21 @@ pub fn set_global_config(matches: &clap::ArgMatches) {
     if matches.is_present("no_verify") {
         config.no_verify = true;
     }
+    if matches.is_present("overflow_check") {
+        config.overflow_check = true;
+    }
+    if matches.is_present("locked_ether") {
+        config.locked_ether = true;
+    }
+    if matches.is_present("privilege_report") {
+        config.privilege_report = true;
+    }
+    if matches.is_present("initializer") {
+        config.initializer = true;
+    }
+    if matches.is_present("tx_origin") {
+        config.tx_origin = true;
+    }
     if matches.is_present("symbolic_storage") {
         config.symbolic_storage = true;
     }
@@ -444,6 +563,26 @@ fn update_analysis_result_from_env(ana: &mut AnalysisResult, env: &Env) {
     }
 }
 
+// Adds the locked ether finding for the transactions explored so far next to any other attacks
+fn report_locked_ether(
+    ana: &mut AnalysisResult,
+    deposit: Option<Vec<TxData>>,
+    transactions: usize,
+    paths: usize,
+) {
+    if let Some(txs) = deposit {
+        ana.attacks.get_or_insert_with(Vec::new).push(Attack {
+            txs,
+            attack_type: AttackType::LockedEther {
+                transactions,
+                paths,
+            },
+            counterexamples: None,
+            callback: None,
+        });
+    }
+}
+
 fn update_analysis_result(mut ana: AnalysisResult, res: ExplorationResult) -> AnalysisResult {
     // update attacks
     if let Some(found_attacks) = res.result {
@@ -502,6 +641,23 @@ fn update_analysis_result(mut ana: AnalysisResult, res: ExplorationResult) -> An
     ana
 }
 
+// Merges what every round reports, whether or not it found attacks
+fn update_round_statistics(ana: &mut AnalysisResult, res: &ExplorationResult) {
+    ana.unsupported_exponentiation |= res.unsupported_exponentiation;
+    ana.unsupported_probes |= res.unsupported_probes;
+
+    // update emitted events
+    if let Some(ref found_events) = res.emitted_events {
+        let mut events = ana.emitted_events.take().unwrap_or_default();
+        for ev in found_events {
+            if !events.contains(ev) {
+                events.push(ev.clone());
+            }
+        }
+        ana.emitted_events = Some(events);
+    }
+}
+
 fn seperators(f: &mut fmt::Formatter) -> fmt::Result {
     writeln!(
         f,
@@ -547,6 +703,20 @@ impl fmt::Display for AnalysisResult {
             }
         }
 
+        if self.unsupported_exponentiation {
+            writeln!(
+                f,
+                "\n\tSymbolic exponents beyond the exponent bound were overapproximated!"
+            )?;
+        }
+
+        if self.unsupported_probes {
+            writeln!(
+                f,
+                "\n\tSome functions were not checked for denial of service beyond the probe bound!"
+            )?;
+        }
+
         if let Some(ref accounts) = self.loaded_accounts {
             writeln!(
                 f,
@@ -560,6 +730,16 @@ impl fmt::Display for AnalysisResult {
                 }
             }
         }
+        if let Some(ref effects) = self.privileged_effects {
+            writeln!(
+                f,
+                "\n\tFound {} state changing operation(s) by selector:",
+                effects.len()
+            )?;
+            for e in effects {
+                writeln!(f, "\t\t- {}", e)?;
+            }
+        }
         seperators(f)?;
         if let Some(ref attacks) = self.attacks {
             writeln!(f, "Found {} attacks(s):", attacks.len())?;
@@ -601,6 +781,24 @@ impl fmt::Display for PrecompiledContracts {
     }
 }
 
+/// A log observed on some successful path, the signature is the first topic if it is concrete
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmittedEvent {
+    pub address: Address,
+    pub topics: usize,
+    pub signature: Option<U256>,
+}
+
+impl fmt::Display for EmittedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LOG{} from {:x}", self.topics, self.address)?;
+        if let Some(ref sig) = self.signature {
+            write!(f, " with signature {:#x}", sig)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LoadedAccount {
     pub id: usize,
@@ -687,12 +885,13 @@ fn analyze_contract_code(code: &[u8], res: &mut AnalysisResult) -> bool {
     res.executed
 }
 
-// codecopy and extcodecopy are also copy instructions, however we only support them in a constant
-// fashion
 fn copy_instructions(op: &Instr) -> bool {
     match op {
         Instr::ICallDataCopy
+        | Instr::ICodeCopy
+        | Instr::IExtCodeCopy
         | Instr::IRDataCopy
+        | Instr::IMCopy
         | Instr::ICall
         | Instr::IStaticCall
         | Instr::ICallCode
@@ -6,11 +6,45 @@ pub const MAX_CALLVAL: &str = "10000000000000000000";
 pub const MAX_GASPRICE: &str = "1000000";
 pub const MAX_GAS: &str = "20000000000000";
 pub const GAS_LIMIT: &str = "20000000000000";
-pub const MAX_DIFFICULTY: &str = "123012123123";
+pub const MAX_BASEFEE: &str = "1000000000000";
+pub const MAX_BLOB_BASEFEE: &str = "1000000000000";
 pub const MAX_NUMBER: &str = "12312312312";
 pub const MAX_TIMESTAMP: &str = "123123123122";
 pub const MAX_CALLDATA_SIZE: &str = "256";
+/// Blobs a transaction can carry at most, i.e. the Cancun limit per block
+pub const MAX_BLOBS: usize = 6;
+/// The KZG version byte every versioned blob hash starts with, shifted into the top byte
+pub const BLOB_HASH_VERSION: &str =
+    "452312848583266388373324160190187140051835877600158453279131187530910662656";
+pub const BLOB_HASH_VERSION_MASK: &str =
+    "115339776388732929035197660848497720713218148788040405586178452820382218977280";
 pub const COINBASE: &str =
     "63567725099261988277993533668138608275708455429142357959792648832739515514623";
 pub const BLOCKHASH: &str =
     "1238602313824588160051031710043776340099843562868198520123683011552894665916";
+pub const MAX_INITCODE_SIZE: usize = 0xC000;
+pub const MAX_CODE_SIZE: usize = 0x6000;
+/// Selectors of the ERC-20 `balanceOf(address)` and `transfer(address,uint256)` functions
+pub const BALANCE_SELECTORS: [u32; 2] = [0x70a0_8231, 0xa905_9cbb];
+/// keccak256("Transfer(address,address,uint256)")
+pub const TRANSFER_TOPIC: &str =
+    "100389287136786176327247604509743168900146139575972864366142685224231313322991";
+pub const ADDRESS_MASK: &str = "1461501637330902918203684832716283019655932542975";
+/// Order of the secp256k1 curve
+pub const SECP256K1_ORDER: &str =
+    "115792089237316195423570985008687907852837564279074904382605163141518161494337";
+/// A signature of the private key 1 over the hash "replayed" as hash, v, r, s and signer, which
+/// the concrete ecrecover precompile accepts
+pub const KNOWN_SIGNATURE: [&str; 5] = [
+    "51742892209565297420421815115109715785817313762917030272793839857771619549184",
+    "27",
+    "89565891926547004231252920425935692360644145829622209833684329913297188986597",
+    "12758347449398053114051875266178750146811947656732167861936503314775323520722",
+    "721457446580647751014191829380889690493307935711",
+];
+/// EIP-1967 implementation, admin and beacon slots
+pub const EIP1967_SLOTS: [&str; 3] = [
+    "24440054405305269366569402256811496959409073762505157381672968839269610695612",
+    "81955473079516046949633743016697847541294818689821282749996681496272635257091",
+    "74152234768234802001998023604048924213078445070507138447576258417738225607073",
+];
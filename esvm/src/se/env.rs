@@ -193,6 +193,13 @@ impl SeEnviroment {
             env.get_account_mut(&id).initial_balance =
                 Some(BitVec::as_revm_u256(&account_balance).unwrap().into());
 
+            if !s["nonce"].is_badvalue() {
+                let nonce = FVal::as_usize(&parse_yaml_value(&s["nonce"])).unwrap();
+                let account = env.get_account_mut(&id);
+                account.nonce = nonce;
+                account.initial_nonce = nonce;
+            }
+
             // save id
             let mut loaded_accounts = env.loaded_accounts.unwrap_or_else(Vec::new);
             loaded_accounts.push(id);
@@ -268,6 +275,10 @@ impl SeEnviroment {
             env.get_account_mut(&id).initial_balance =
                 Some(BitVec::as_revm_u256(&account_balance).unwrap());
 
+            let account = env.get_account_mut(&id);
+            account.nonce = acc.info.nonce as usize;
+            account.initial_nonce = acc.info.nonce as usize;
+
             // save id
             let mut loaded_accounts = env.loaded_accounts.unwrap_or_else(Vec::new);
             loaded_accounts.push(id);
@@ -300,7 +311,11 @@ pub struct Block {
     pub gasprice: BVal,
     pub mem_size: BVal,
     pub gas_limit: BVal,
-    pub difficulty: BVal,
+    pub prevrandao: BVal,
+    pub basefee: BVal,
+    pub blob_basefee: BVal,
+    /// Versioned hashes of the blobs carried by the transaction, zero past the last blob
+    pub blobhashes: Vec<BVal>,
     pub number: BVal,
     pub timestamp: BVal,
     pub coinbase: BVal,
@@ -317,7 +332,10 @@ impl Block {
         let gasprice = fresh_var("gasprice");
         let mem_size = fresh_var("mem_size");
         let gas_limit = fresh_var("gas_limit");
-        let difficulty = fresh_var("difficulty");
+        let prevrandao = fresh_var("prevrandao");
+        let basefee = fresh_var("basefee");
+        let blob_basefee = fresh_var("blob_basefee");
+        let blobhashes = Block::fresh_blobhashes();
         let number = fresh_var("number");
         let timestamp = fresh_var("timestamp");
         let coinbase = fresh_var("coinbase");
@@ -328,7 +346,10 @@ impl Block {
             gasprice,
             mem_size,
             gas_limit,
-            difficulty,
+            prevrandao,
+            basefee,
+            blob_basefee,
+            blobhashes,
             number,
             timestamp,
             coinbase,
@@ -337,6 +358,36 @@ impl Block {
             chainid,
         }
     }
+
+    /// The hashes of the first `count` blobs, the transaction carries no further blobs
+    pub fn carried_blobhashes(&self, count: usize) -> &[BVal] {
+        &self.blobhashes[..count.min(MAX_BLOBS)]
+    }
+
+    fn fresh_blobhashes() -> Vec<BVal> {
+        (0..MAX_BLOBS)
+            .map(|i| fresh_var(&format!("blobhash_{}", i)))
+            .collect()
+    }
+
+    /// Every blob hash is either zero or versioned, and only the trailing ones can be zero
+    fn blob_constraints(&self) -> Vec<BVal> {
+        let mut constraints = vec![];
+        for (i, hash) in self.blobhashes.iter().enumerate() {
+            let present = neql(hash, &zero());
+            constraints.push(implies(
+                &present,
+                &eql(
+                    &and(hash, &const256(BLOB_HASH_VERSION_MASK)),
+                    &const256(BLOB_HASH_VERSION),
+                ),
+            ));
+            if i > 0 {
+                constraints.push(implies(&present, &neql(&self.blobhashes[i - 1], &zero())));
+            }
+        }
+        constraints
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -363,6 +414,11 @@ pub struct Env {
     transactions: HashMap<TxId, Transaction>,
     tx_counter: usize,
     constraints: Vec<BVal>,
+
+    /// Calls from the hijack account back into the victim, in the order they were issued
+    reentrancy_txs: Vec<TxId>,
+    /// The balance of the re-entered account before it was re-entered and after the call returned
+    reentrancy_balances: Option<(BVal, BVal)>,
 }
 
 impl Default for Env {
@@ -386,18 +442,23 @@ impl Env {
 
         let transactions = HashMap::new();
         let tx_counter = 0;
+        let reentrancy_txs = vec![];
+        let reentrancy_balances = None;
         let block = Block::new();
 
-        let constraints = vec![
+        // prevrandao stays unconstrained, unlike the old difficulty it can take any value
+        let mut constraints = vec![
             lt(&block.gasprice, &const256(MAX_GASPRICE)),
             lt(&block.gas_limit, &const256(GAS_LIMIT)),
             lt(&block.mem_size, &const_usize(100_000)),
-            lt(&block.difficulty, &const256(MAX_DIFFICULTY)),
+            lt(&block.basefee, &const256(MAX_BASEFEE)),
+            lt(&block.blob_basefee, &const256(MAX_BLOB_BASEFEE)),
             lt(&block.number, &const256(MAX_NUMBER)),
             lt(&block.timestamp, &const256(MAX_TIMESTAMP)),
             eql(&block.coinbase, &const256(COINBASE)),
             eql(&block.blockhash, &const256(BLOCKHASH)),
         ];
+        constraints.append(&mut block.blob_constraints());
         let blocks = vec![block];
         let blockhashes = None;
         Env {
@@ -413,6 +474,8 @@ impl Env {
             precompiled_contracts,
             blocks,
             blockhashes,
+            reentrancy_txs,
+            reentrancy_balances,
         }
     }
 
@@ -432,7 +495,10 @@ impl Env {
         env.latest_block_mut().gasprice = fresh_var("gasprice");
         env.latest_block_mut().mem_size = fresh_var("mem_size");
         env.latest_block_mut().gas_limit = fresh_var("gas_limit");
-        env.latest_block_mut().difficulty = fresh_var("difficulty");
+        env.latest_block_mut().prevrandao = fresh_var("prevrandao");
+        env.latest_block_mut().basefee = fresh_var("basefee");
+        env.latest_block_mut().blob_basefee = fresh_var("blob_basefee");
+        env.latest_block_mut().blobhashes = Block::fresh_blobhashes();
         env.latest_block_mut().number = fresh_var("number");
         env.latest_block_mut().timestamp = fresh_var("timestamp");
         env.latest_block_mut().coinbase = fresh_var("coinbase");
@@ -441,9 +507,10 @@ impl Env {
         constraints.push(lt(&env.latest_block().gasprice, &const256(MAX_GASPRICE)));
         constraints.push(lt(&env.latest_block().gas_limit, &const256(GAS_LIMIT)));
         constraints.push(lt(&env.latest_block().mem_size, &const_usize(100_000)));
+        constraints.push(lt(&env.latest_block().basefee, &const256(MAX_BASEFEE)));
         constraints.push(lt(
-            &env.latest_block().difficulty,
-            &const256(MAX_DIFFICULTY),
+            &env.latest_block().blob_basefee,
+            &const256(MAX_BLOB_BASEFEE),
         ));
         constraints.push(lt(
             &old_env.latest_block().number,
@@ -460,6 +527,7 @@ impl Env {
             &generate_random_address(),
         ));
         constraints.push(eql(&env.latest_block().blockhash, &generate_random_hash()));
+        constraints.append(&mut env.latest_block().blob_constraints());
         env.constraints.append(&mut constraints);
         env
     }
@@ -480,6 +548,7 @@ impl Env {
                 mems.push(*map);
             }
             mems.push(acc.storage);
+            mems.push(acc.transient);
         }
         for tx in self.transactions.values() {
             mems.push(tx.data);
@@ -515,6 +584,11 @@ impl Env {
         let victim_addr = self.accounts[&victim].addr.clone();
         let tx_id = self.new_tx_id();
 
+        // transient storage does not survive across transactions
+        for acc in self.accounts.values_mut() {
+            acc.transient = new_transient_storage(memory, &acc.name, acc.id);
+        }
+
         let tx = Transaction::with_sender_receiver(
             memory,
             tx_id,
@@ -525,6 +599,95 @@ impl Env {
         self.update_env_for_tx(&attacker, &victim, tx, tx_id)
     }
 
+    /// An attacker transaction relayed through the hijack account, e.g. after luring a privileged
+    /// account into calling it, thus the origin is an arbitrary account other than the caller
+    pub fn new_relayed_tx(&mut self, memory: &mut SymbolicMemory, victim: AccountId) -> TxId {
+        let hijack = *self.try_get_account_id_by_addr(&const256(HIJACK_ADDR)).unwrap();
+        let hijack_addr = self.accounts[&hijack].addr.clone();
+        let victim_addr = self.accounts[&victim].addr.clone();
+        let tx_id = self.new_tx_id();
+
+        // transient storage does not survive across transactions
+        for acc in self.accounts.values_mut() {
+            acc.transient = new_transient_storage(memory, &acc.name, acc.id);
+        }
+
+        let name = fresh_tx_name("relayed");
+        let mut tx =
+            Transaction::with_sender_receiver(memory, tx_id, &name, &hijack_addr, &victim_addr);
+        tx.origin = fresh_var(&format!("{}_origin", name));
+        tx.constraints.push(neql(&tx.origin, &hijack_addr));
+        tx.constraints.push(neql(&tx.origin, &victim_addr));
+        tx.constraints.push(eql(&tx.origin, &and(&tx.origin, &const256(ADDRESS_MASK))));
+        self.update_env_for_tx(&hijack, &victim, tx, tx_id)
+    }
+
+    /// A transaction without value of an uninvolved account, i.e. neither attacker, hijack nor
+    /// victim, used to check whether functions stay usable for everyone else
+    pub fn new_honest_tx(
+        &mut self,
+        memory: &mut SymbolicMemory,
+        attacker: AccountId,
+        victim: AccountId,
+    ) -> TxId {
+        let hijack = *self.try_get_account_id_by_addr(&const256(HIJACK_ADDR)).unwrap();
+        let hijack_addr = self.accounts[&hijack].addr.clone();
+        let attacker_addr = self.accounts[&attacker].addr.clone();
+        let victim_addr = self.accounts[&victim].addr.clone();
+        let tx_id = self.new_tx_id();
+
+        // transient storage does not survive across transactions
+        for acc in self.accounts.values_mut() {
+            acc.transient = new_transient_storage(memory, &acc.name, acc.id);
+        }
+
+        let name = fresh_tx_name("honest");
+        let caller = fresh_var(&format!("{}_sender", name));
+        let mut tx = Transaction::with_sender_receiver(memory, tx_id, &name, &caller, &victim_addr);
+        for addr in &[attacker_addr, hijack_addr, victim_addr] {
+            tx.constraints.push(neql(&caller, addr));
+        }
+        tx.constraints.push(eql(&caller, &and(&caller, &const256(ADDRESS_MASK))));
+        tx.constraints.push(eql(&tx.callvalue, &zero()));
+        self.transactions.insert(tx_id, tx);
+        tx_id
+    }
+
+    pub fn new_reentrancy_tx(
+        &mut self,
+        memory: &mut SymbolicMemory,
+        hijack: AccountId,
+        victim: AccountId,
+        origin: &BVal,
+    ) -> TxId {
+        let hijack_addr = self.accounts[&hijack].addr.clone();
+        let victim_addr = self.accounts[&victim].addr.clone();
+        let tx_id = self.new_tx_id();
+
+        let mut tx = Transaction::with_sender_receiver(
+            memory,
+            tx_id,
+            &fresh_tx_name("reentrancy"),
+            &hijack_addr,
+            &victim_addr,
+        );
+        tx.origin = Arc::clone(origin);
+        self.reentrancy_txs.push(tx_id);
+        self.update_env_for_tx(&hijack, &victim, tx, tx_id)
+    }
+
+    pub fn reentrancy_txs(&self) -> &[TxId] {
+        &self.reentrancy_txs
+    }
+
+    pub fn record_reentrancy_balances(&mut self, before: &BVal, after: &BVal) {
+        self.reentrancy_balances = Some((Arc::clone(before), Arc::clone(after)));
+    }
+
+    pub fn reentrancy_balances(&self) -> Option<&(BVal, BVal)> {
+        self.reentrancy_balances.as_ref()
+    }
+
     fn update_env_for_tx(
         &mut self,
         from: &AccountId,
@@ -587,12 +750,13 @@ impl Env {
 
     pub fn new_hijack_account(&mut self, memory: &mut SymbolicMemory) -> AccountId {
         let id = self.new_acc_id();
-        let acc = Account::with_addr(
+        let mut acc = Account::with_addr(
             memory,
             id,
             &fresh_account_name("hijack"),
             &const256(HIJACK_ADDR),
         );
+        acc.initial_attacker_balance = Some(Arc::clone(&acc.balance));
         self.accounts.insert(id, acc);
         self.addresses.insert(const256(HIJACK_ADDR), id);
         id
@@ -628,6 +792,22 @@ impl Env {
         id
     }
 
+    /// Replaces the storage of the account with zeroed storage, as right after its deployment
+    pub fn zero_storage(&mut self, memory: &mut SymbolicMemory, id: &AccountId) {
+        let account = self.get_account_mut(id);
+        let root = symbolic_memory::create_new_memory(
+            memory,
+            fresh_var_name(&format!("{}_storage", account.name)),
+            MemoryType::Storage,
+            None,
+            Some(*id),
+        );
+        account.storage =
+            symbolic_memory::memset_unlimited(memory, root, &const_usize(0), Some(const_usize(0)));
+        account.initial_storage = None;
+        account.mappings = Arc::new(HashMap::new());
+    }
+
     pub fn new_account(
         &mut self,
         memory: &mut SymbolicMemory,
@@ -654,6 +834,17 @@ impl Env {
         id
     }
 
+    /// Replace the code of an account, e.g. with the runtime code returned by its init code
+    pub fn set_code(&mut self, id: &AccountId, code: Option<Vec<u8>>) {
+        if let Some(ref c) = code {
+            let coverage = CodeCoverage::from_raw(c);
+            GLOBAL_COVERAGE_MAP.lock().insert(*id, coverage);
+        }
+        let acc = self.get_account_mut(id);
+        acc.codesize = code.as_ref().map_or(0, |c| c.len());
+        acc.code = code;
+    }
+
     pub fn get_addresses_except(&self, id: &AccountId) -> Vec<(BVal, AccountId)> {
         self.addresses
             .iter()
@@ -816,6 +1007,16 @@ impl Transaction {
     }
 }
 
+fn new_transient_storage(memory: &mut SymbolicMemory, name: &str, id: AccountId) -> MVal {
+    symbolic_memory::create_new_memory(
+        memory,
+        fresh_var_name(&format!("{}_transient", name)),
+        MemoryType::Transient,
+        None,
+        Some(id),
+    )
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Account {
     pub id: AccountId,
@@ -824,12 +1025,16 @@ pub struct Account {
     pub addr: BVal, // former our_addr
     pub balance: BVal,
     pub storage: MVal,
+    pub transient: MVal, // EIP-1153 transient storage of the current transaction
     pub mappings: Arc<HashMap<BVal, MVal>>,
     pub selfdestruct: bool,
     pub owner: Option<BVal>, // index for owner variable
     pub initial_storage: Option<Vec<(U256, U256)>>,
     pub initial_balance: Option<U256>,
     pub initial_attacker_balance: Option<BVal>,
+    pub created: bool, // deployed during the analysis via CREATE/CREATE2
+    pub nonce: usize,
+    pub initial_nonce: usize,
     code: Option<Vec<u8>>,
     codesize: usize,
 
@@ -848,6 +1053,7 @@ impl Account {
             None,
             Some(id),
         );
+        let transient = new_transient_storage(memory, &name, id);
         let constraints = vec![];
         let selfdestruct = false;
         let codesize = match code.clone() {
@@ -859,6 +1065,10 @@ impl Account {
         let initial_storage = None;
         let initial_balance = None;
         let initial_attacker_balance = None;
+        let created = false;
+        // contract accounts start with nonce 1, see EIP-161
+        let nonce = if code.is_some() { 1 } else { 0 };
+        let initial_nonce = nonce;
 
         Account {
             id,
@@ -866,6 +1076,7 @@ impl Account {
             addr,
             balance,
             storage,
+            transient,
             constraints,
             selfdestruct,
             code,
@@ -875,6 +1086,9 @@ impl Account {
             initial_storage,
             initial_balance,
             initial_attacker_balance,
+            created,
+            nonce,
+            initial_nonce,
         }
     }
 
@@ -942,6 +1156,10 @@ impl Into<genesis::Genesis> for Env {
         let mut g = genesis::Genesis::new();
 
         for (_, account) in self.accounts {
+            // created accounts get deployed again when replaying the transactions
+            if account.created {
+                continue;
+            }
             let addr_bytes: [u8; 32] = BitVec::as_bigint(&account.addr).unwrap().into();
             let addr = Address::from_slice(&addr_bytes[12..32]);
             g.add_account(addr, account.into());
@@ -966,7 +1184,7 @@ impl Into<genesis::Account> for Account {
             self.initial_balance
                 .unwrap_or_else(|| U256::from(10_000_000_000_000_000_000u64)), // pre initialize accounts with 10 ether
             self.code.map(|c| c.into()),
-            U256::from(0u64), // nonce does not matter since we do not model account creation
+            U256::from(self.initial_nonce as u64),
             Some(storage),
         )
     }
@@ -1069,6 +1287,40 @@ victim: 0x780771f6a176a937e45d491d180df424d9e15fa6";
         assert_eq!(correct_to_balance, to.balance);
     }
 
+    #[test]
+    fn zero_storage_test() {
+        let mut env = Env::new();
+        let mut memory = symbolic_memory::new_memory();
+        let victim = env.new_victim_account(&mut memory, &[0x00]);
+        let account = env.get_account_mut(&victim);
+        account.storage = word_write(&mut memory, account.storage, &zero(), &one());
+        account.initial_storage = Some(vec![]);
+        let storage = account.storage;
+
+        env.zero_storage(&mut memory, &victim);
+        let account = env.get_account(&victim);
+        assert_ne!(storage, account.storage);
+        assert_eq!(None, account.initial_storage);
+    }
+
+    #[test]
+    fn honest_tx_test() {
+        let mut env = Env::new();
+        let mut memory = symbolic_memory::new_memory();
+        env.new_hijack_account(&mut memory);
+        let attacker = env.new_attacker_account(&mut memory);
+        let victim = env.new_victim_account(&mut memory, &[0x00]);
+        let attacker_balance = Arc::clone(&env.get_account(&attacker).balance);
+
+        let tx_id = env.new_honest_tx(&mut memory, attacker, victim);
+        let tx = env.get_tx(&tx_id);
+        assert!(tx
+            .constraints
+            .contains(&neql(&tx.caller, &env.get_account(&attacker).addr)));
+        assert!(tx.constraints.contains(&eql(&tx.callvalue, &zero())));
+        assert_eq!(attacker_balance, env.get_account(&attacker).balance);
+    }
+
     #[test]
     fn generate_address_test() {
         for _ in 0..1000 {
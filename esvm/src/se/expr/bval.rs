@@ -291,7 +291,7 @@ fn try_load_concrete_storage(memory: &SymbolicMemory, mem: MVal, addr: &BVal) ->
     }
     debug_assert!({
         let mem = &memory[mem];
-        mem.memory_type == MemoryType::Storage
+        mem.memory_type == MemoryType::Storage || mem.memory_type == MemoryType::Transient
     });
 
     lookup_storage_recursive(memory, mem, addr)
@@ -299,7 +299,9 @@ fn try_load_concrete_storage(memory: &SymbolicMemory, mem: MVal, addr: &BVal) ->
 
 fn lookup_storage_recursive(memory: &SymbolicMemory, node: MVal, addr: &BVal) -> Option<BVal> {
     let mem = &memory[node];
-    debug_assert!({ mem.memory_type == MemoryType::Storage });
+    debug_assert!({
+        mem.memory_type == MemoryType::Storage || mem.memory_type == MemoryType::Transient
+    });
 
     match mem.op {
         MemoryOperation::Init => Some(zero()),
@@ -441,6 +443,30 @@ fn lookup_mem_recursive(memory: &SymbolicMemory, node: MVal, addr: &BVal) -> Opt
                 _ => unreachable!(),
             }
         }
+        MemoryOperation::Memcopy {
+            parent: par,
+            from,
+            index: ref old_addr,
+            ref index_from,
+            ref size,
+        } => {
+            if !(FVal::is_constant(old_addr)
+                && FVal::is_constant(index_from)
+                && FVal::is_constant(size))
+            {
+                return None;
+            }
+            // follow the copy into the source memory if the address lies within the copied range
+            match (
+                FVal::check_truth(&le(old_addr, addr)),
+                FVal::check_truth(&lt(addr, &add(old_addr, size))),
+            ) {
+                (SymbolicTruth::True, SymbolicTruth::True) => {
+                    lookup_mem_recursive(memory, from, &add(index_from, &sub(addr, old_addr)))
+                }
+                _ => lookup_mem_recursive(memory, par, addr),
+            }
+        }
 
         _ => None,
     }
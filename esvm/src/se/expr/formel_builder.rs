@@ -338,7 +338,10 @@ impl<'a> SmtLib2Builder<'a> {
     }
 
     fn assert_memory_versions(&mut self, node: MVal) {
-        if self.memory[node].memory_type == MemoryType::Storage {
+        if std::matches!(
+            self.memory[node].memory_type,
+            MemoryType::Storage | MemoryType::Transient
+        ) {
             return self.assert_storage_versions(node);
         }
         self.assert_memory(node);
@@ -522,7 +525,10 @@ impl<'a> SmtLib2Builder<'a> {
             } => {
                 // recursively collect prior updates
                 self.assert_memory(par);
-                self.assert_memory(from);
+                // MCOPY copies within the same memory
+                if from != par {
+                    self.assert_memory(from);
+                }
 
                 let reads =
                     get_needed_read_indices_cached(self.reads, self.memory, node, self.read_cache);
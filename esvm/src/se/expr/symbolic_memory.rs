@@ -43,6 +43,10 @@ pub fn create_new_memory(
         MemoryType::Data => {
             memset_unlimited(memory, root, &calldata_size.unwrap(), Some(const_usize(0)))
         }
+        // transient storage is always zero at the start of a transaction
+        MemoryType::Transient => {
+            memset_unlimited(memory, root, &const_usize(0), Some(const_usize(0)))
+        }
     }
 }
 
@@ -226,7 +230,7 @@ impl MemoryInfo {
 
     pub fn elem_size(&self) -> usize {
         match self.memory_type {
-            MemoryType::Storage => 256,
+            MemoryType::Storage | MemoryType::Transient => 256,
             _ => 8,
         }
     }
@@ -278,6 +282,7 @@ pub enum MemoryType {
     Memory,
     Data,
     Storage,
+    Transient, // EIP-1153, discarded after every transaction
 }
 
 pub fn byte_write(memory: &mut SymbolicMemory, parent: NodeIndex, addr: &BVal, val: &BVal) -> MVal {
@@ -1,15 +1,18 @@
 use std::{
-    collections::HashSet, fmt, fs::File, str::FromStr, sync::{
-        atomic::{AtomicUsize, Ordering},
+    collections::{HashMap, HashSet}, fmt, fs::File, str::FromStr, sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex, RwLock,
     }
 };
 
-use revm::primitives::{Address, Bytes, U256};
+use revm::{
+    bytecode::Bytecode,
+    primitives::{Address, Bytes, U256},
+};
 use evmexec::{
     evmtrace::Instruction,
     genesis::Genesis,
-    evm::{Evm, EvmInput, ForgeInput},
+    evm::{BlockInput, Evm, EvmInput, ForgeInput},
 };
 use num_cpus;
 use rayon::prelude::*;
@@ -26,9 +29,11 @@ use crate::se::{
         symbolic_memory::{MVal, MemoryType, SymbolicMemory},
     },
     symbolic_graph::SymbolicGraph,
-    symbolic_state::{Flags, HaltingReason, ResultState, SeState},
+    symbolic_state::{
+        BlockDependentEffect, Effect, Flags, HaltingReason, ResultState, RevertReason, SeState,
+    },
 };
-use crate::{LoadedAccount, PrecompiledContracts, convert_fval_to_address};
+use crate::{EmittedEvent, LoadedAccount, PrecompiledContracts, convert_fval_to_address};
 
 #[cfg(test)]
 use crate::se::expr::symbolic_memory::{self};
@@ -39,6 +44,9 @@ lazy_static! {
     pub static ref CONFIG: RwLock<SeConfig> = RwLock::new(SeConfig::new());
 }
 
+// Upper bound of distinct honest call probes after the end states of one exploration
+const MAX_PROBES: usize = 256;
+
 #[derive(Clone, Debug)]
 pub struct SeConfig {
     /// General Config Options
@@ -53,6 +61,56 @@ pub struct SeConfig {
     /// Sets the message bound for exploration. Standard is 5 iterations.
     pub message_bound: usize,
 
+    /// Sets the upper bound for case splitting on symbolic exponents. Standard is 18, i.e. the
+    /// usual token decimals.
+    pub exp_bound: usize,
+
+    /// Sets the number of blobs a transaction may carry, at most `MAX_BLOBS`. Standard is 1.
+    pub blob_count: usize,
+
+    /// The Solidity panic codes which are reported as violations. Standard is assert (0x01),
+    /// arithmetic (0x11), division by zero (0x12), enum conversion (0x21), empty array pop
+    /// (0x31), array out-of-bounds (0x32) and memory allocation (0x41) failures are opt-in.
+    pub panic_codes: Vec<usize>,
+
+    /// Report wrapping add, sub and mul results which are stored or sent as call value
+    pub overflow_check: bool,
+
+    /// Revert reasons which must never be reachable, e.g. custom errors signalling a broken
+    /// invariant
+    pub forbidden_reverts: Vec<RevertReason>,
+
+    /// Event signatures, i.e. first topics, which the victim must never emit
+    pub forbidden_events: Vec<U256>,
+
+    /// Additionally explore attacker transactions relayed through the hijack account, so the
+    /// transaction origin can be a phished privileged account
+    pub tx_origin: bool,
+
+    /// Storage slots which must not be overwritable through an attacker controlled slot, if
+    /// empty any slot the attacker can choose freely is reported
+    pub critical_slots: Vec<U256>,
+
+    /// Storage slots of token balance mappings, e.g. `balanceOf`, if empty the mappings the
+    /// victim's balanceOf and transfer functions read are used
+    pub balance_slots: Vec<U256>,
+
+    /// Report contracts which can receive ether but never send it out within the bounds
+    pub locked_ether: bool,
+
+    /// Report the state changing operations reachable through each function selector of the
+    /// first transaction
+    pub privilege_report: bool,
+
+    /// Function selectors which must stay callable by honest accounts after attacker
+    /// transactions
+    pub dos_selectors: Vec<u32>,
+
+    /// Additionally explore the first transaction on the victim with zeroed storage, as if freshly
+    /// deployed, and report those which set its owner, admin or implementation slots to the
+    /// attacker
+    pub initializer: bool,
+
     /// Sets the solver timeout. Standard is 2 Minutes.
     pub solver_timeout: usize,
 
@@ -112,6 +170,19 @@ impl SeConfig {
             loop_bound: 5,
             call_depth_limit: 3,
             message_bound: 5,
+            exp_bound: 18,
+            blob_count: 1,
+            panic_codes: vec![0x01],
+            overflow_check: false,
+            forbidden_reverts: vec![],
+            forbidden_events: vec![],
+            critical_slots: vec![],
+            balance_slots: vec![],
+            locked_ether: false,
+            privilege_report: false,
+            dos_selectors: vec![],
+            initializer: false,
+            tx_origin: false,
             dgraph: false,
             no_verify: false,
             symbolic_storage: false,
@@ -137,10 +208,19 @@ pub struct Context {
 
     /// The initial storage for the executed account, saved in case we reach a REVERT statement
     initial_storage: MVal,
+
+    /// The initial transient storage for the executed account, restored on REVERT as well
+    initial_transient: MVal,
 }
 
 impl Context {
-    pub fn new(config: SeConfig, disasm: Disasm, initial_storage: MVal, solvers: Solvers) -> Self {
+    pub fn new(
+        config: SeConfig,
+        disasm: Disasm,
+        initial_storage: MVal,
+        initial_transient: MVal,
+        solvers: Solvers,
+    ) -> Self {
         let solver_pool = create_pool(solvers);
         let id_generator = AtomicUsize::new(1);
         Self {
@@ -149,6 +229,7 @@ impl Context {
             id_generator,
             disasm,
             initial_storage,
+            initial_transient,
         }
     }
 
@@ -164,10 +245,18 @@ impl Context {
             None,
             None,
         );
+        let initial_transient = symbolic_memory::create_new_memory(
+            &mut memory,
+            "test_transient".to_string(),
+            MemoryType::Transient,
+            None,
+            None,
+        );
         Self::new(
             config,
             disasm,
             initial_storage,
+            initial_transient,
             Solvers::Yice {
                 count: num_cpus::get(),
                 timeout: 120_000,
@@ -200,10 +289,20 @@ impl Context {
         self.disasm.jump_targets()
     }
 
+    /// Whether the code pushes the selector, i.e. dispatches on it
+    pub fn dispatches(&self, selector: u32) -> bool {
+        let push = Instr::IPush(selector.to_be_bytes().to_vec());
+        self.disasm.opcodes().any(|ins| *ins == push)
+    }
+
     pub fn initial_storage(&self) -> MVal {
         self.initial_storage
     }
 
+    pub fn initial_transient(&self) -> MVal {
+        self.initial_transient
+    }
+
     pub fn solver_pool(&self) -> Arc<SolverPool> {
         Arc::clone(&self.solver_pool)
     }
@@ -212,6 +311,14 @@ impl Context {
 pub enum AnalysisMode {
     Execution,
     Call(TxId),
+    /// A call of the selector by an honest account
+    Probe(u32),
+    /// An attacker transaction relayed through the hijack account, only explored for the
+    /// tx.origin detector
+    Relayed,
+    /// The first attacker transaction on the victim with zeroed storage, only explored for the
+    /// initializer detector
+    Initializer,
 }
 
 impl AnalysisMode {
@@ -230,6 +337,30 @@ impl AnalysisMode {
             false
         }
     }
+
+    fn is_probe(&self) -> bool {
+        if let AnalysisMode::Probe(_) = self {
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_relayed(&self) -> bool {
+        if let AnalysisMode::Relayed = self {
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_initializer(&self) -> bool {
+        if let AnalysisMode::Initializer = self {
+            true
+        } else {
+            false
+        }
+    }
 }
 
 pub struct Analysis {
@@ -239,6 +370,16 @@ pub struct Analysis {
     mode: AnalysisMode,
     end_states: Option<Vec<SeState>>,
     blocks: Option<Vec<usize>>,
+    /// The same transaction relayed through the hijack account if the tx.origin detector is on
+    relayed: Option<Box<Analysis>>,
+    /// The first transaction on the victim as if freshly deployed if the initializer detector is on
+    initializer: Option<Box<Analysis>>,
+    /// Whether a selector stays callable, by selector and the victim's storage and balance
+    probes: Mutex<HashMap<(u32, MVal, BVal), bool>>,
+    /// Some selector was left undecided since the bound of probes was reached
+    probe_bound_reached: AtomicBool,
+    /// Storage slots of the token balance mappings checked for the attacker
+    balance_slots: Vec<U256>,
 }
 
 impl Analysis {
@@ -255,14 +396,21 @@ impl Analysis {
         memory: Arc<SymbolicMemory>,
     ) -> Self {
         let initial_storage = env.get_account(&to).storage;
+        let initial_transient = env.get_account(&to).transient;
         let blocks = env.blocknumbers.clone();
+        let balance_slots = config.balance_slots.clone();
         let context = Arc::new(Context::new(
             config,
             Disasm::from_raw(code),
             initial_storage,
+            initial_transient,
             solvers,
         ));
-        let graph = Self::create_graph(env, initial_tx, &to, init_state, context, memory);
+        let probe = match &mode {
+            AnalysisMode::Probe(selector) => Some(*selector),
+            _ => None,
+        };
+        let graph = Self::create_graph(env, initial_tx, &to, init_state, probe, context, memory);
         let end_states = None;
 
         Self {
@@ -272,6 +420,11 @@ impl Analysis {
             mode,
             end_states,
             blocks,
+            relayed: None,
+            initializer: None,
+            probes: Mutex::new(HashMap::new()),
+            probe_bound_reached: AtomicBool::new(false),
+            balance_slots,
         }
     }
 
@@ -283,19 +436,77 @@ impl Analysis {
             mut memory,
         } = se_env;
         let code = env.get_account(&to).code().cloned().unwrap();
+        // initializer guards, e.g. OpenZeppelin's `_initialized`, are judged before deployment
+        let initializer = if config.initializer {
+            let mut env = env.clone();
+            let mut memory = Arc::clone(&memory);
+            env.zero_storage(Arc::make_mut(&mut memory), &to);
+            Some((env, memory))
+        } else {
+            None
+        };
+        let relayed = if config.tx_origin {
+            Some((env.clone(), Arc::clone(&memory)))
+        } else {
+            None
+        };
         let initial_tx = env.new_attacker_tx(Arc::make_mut(&mut memory), from, to);
-        Self::new(
+        let mut analysis = Self::new(
             &code,
             Arc::new(env),
             &initial_tx,
             from,
             to,
             None,
-            config,
+            config.clone(),
             solvers,
             AnalysisMode::Execution,
             memory,
-        )
+        );
+        if let Some((mut env, mut memory)) = initializer {
+            let initial_tx = env.new_attacker_tx(Arc::make_mut(&mut memory), from, to);
+            let solvers =
+                Solvers::Initialized(analysis.graph.initial_state().context.solver_pool());
+            analysis.initializer = Some(Box::new(Self::new(
+                &code,
+                Arc::new(env),
+                &initial_tx,
+                from,
+                to,
+                None,
+                config.clone(),
+                solvers,
+                AnalysisMode::Initializer,
+                memory,
+            )));
+        }
+        if let Some((mut env, mut memory)) = relayed {
+            let initial_tx = env.new_relayed_tx(Arc::make_mut(&mut memory), to);
+            let solvers =
+                Solvers::Initialized(analysis.graph.initial_state().context.solver_pool());
+            analysis.relayed = Some(Box::new(Self::new(
+                &code,
+                Arc::new(env),
+                &initial_tx,
+                from,
+                to,
+                None,
+                config,
+                solvers,
+                AnalysisMode::Relayed,
+                memory,
+            )));
+        }
+        // token balances are the mappings read by balanceOf and transfer unless supplied
+        let context = &analysis.graph.initial_state().context;
+        if analysis.balance_slots.is_empty()
+            && crate::se::config::BALANCE_SELECTORS
+                .iter()
+                .all(|selector| context.dispatches(*selector))
+        {
+            analysis.balance_slots = analysis.balance_mappings(analysis.graph.initial_state());
+        }
+        analysis
     }
 
     pub fn from_result_state(
@@ -307,6 +518,19 @@ impl Analysis {
         mode: AnalysisMode,
         mut memory: Arc<SymbolicMemory>,
     ) -> Self {
+        let relayed = if mode.is_execution() && config.tx_origin {
+            Some(Box::new(Self::from_result_state(
+                code,
+                from,
+                to,
+                config.clone(),
+                state.clone(),
+                AnalysisMode::Relayed,
+                Arc::clone(&memory),
+            )))
+        } else {
+            None
+        };
         let mut env = Arc::clone(&state.env);
         let initial_tx;
         {
@@ -315,13 +539,17 @@ impl Analysis {
                 AnalysisMode::Execution => {
                     Arc::make_mut(&mut env).new_attacker_tx(memory_ptr, *from, *to)
                 }
+                AnalysisMode::Relayed => Arc::make_mut(&mut env).new_relayed_tx(memory_ptr, *to),
                 AnalysisMode::Call(id) => *id,
+                AnalysisMode::Probe(_) => {
+                    Arc::make_mut(&mut env).new_honest_tx(memory_ptr, *from, *to)
+                }
             };
         }
 
         let solvers = Solvers::Initialized(Arc::clone(&state.solver_pool));
 
-        Self::new(
+        let mut analysis = Self::new(
             code,
             env,
             &initial_tx,
@@ -332,19 +560,29 @@ impl Analysis {
             solvers,
             mode,
             memory,
-        )
+        );
+        analysis.relayed = relayed;
+        analysis
     }
 
     pub fn symbolic_round(&mut self) {
-        assert!(self.mode.is_execution());
+        assert!(self.mode.is_execution() || self.mode.is_relayed() || self.mode.is_initializer());
         self.graph.analyze_graph();
 
         self.end_states = Some(self.graph.end_states());
+        if let Some(relayed) = self.relayed.as_mut() {
+            relayed.symbolic_round();
+        }
+        if let Some(initializer) = self.initializer.as_mut() {
+            initializer.symbolic_round();
+        }
     }
 
     pub fn exploration_result(mut self) -> ExplorationResult {
         assert!(self.end_states.is_some());
         let end_states = self.end_states.take().unwrap();
+        let relayed = self.relayed.take();
+        let initializer = self.initializer.take();
         info!(
             "Found {} potential attack states, analyzing...",
             end_states.len()
@@ -352,9 +590,52 @@ impl Analysis {
         let result = Mutex::new(vec![]);
         let precompiled_contracts = Mutex::new(vec![]);
         let loaded_accounts = Mutex::new(vec![]);
+        let unsupported_exponentiation = AtomicBool::new(false);
+        let emitted_events = Mutex::new(HashSet::new());
+        let deposit = Mutex::new(None);
+        let withdrawal = AtomicBool::new(false);
+        let explored_paths = end_states.len();
+        let privileged_effects = Mutex::new(HashMap::new());
+        let report_privileges = self.graph.initial_state().config().privilege_report
+            && self.graph.initial_state().previous_tx.is_empty();
+        // functions an honest account can call before this transaction, with such a call
+        let callable: Vec<(u32, Address, TxData)> = self
+            .graph
+            .initial_state()
+            .config()
+            .dos_selectors
+            .iter()
+            .filter_map(|selector| {
+                let (sender, call) = self.callable_after(self.graph.initial_state(), *selector)?;
+                Some((*selector, sender, call))
+            })
+            .collect();
+        // access control slots inferred on one path are checked against all other paths
+        let privileged_slots: HashSet<BVal> = end_states
+            .iter()
+            .flat_map(|s| s.privileged_slots.iter())
+            .filter(|(acc, _)| *acc == self.to)
+            .map(|(_, slot)| Arc::clone(slot))
+            .collect();
         end_states
             .into_par_iter()
             .for_each(|potential_attack_state| {
+                if potential_attack_state.flags.contains(Flags::UNSUPPORTED_EXP) {
+                    unsupported_exponentiation.store(true, Ordering::Relaxed);
+                }
+                if !potential_attack_state.logs.is_empty() {
+                    let mut events = emitted_events.lock().unwrap();
+                    for log in potential_attack_state.logs.iter() {
+                        let addr = &potential_attack_state.env.get_account(&log.account).addr;
+                        if let Some(address) = FVal::as_revm_u256(addr) {
+                            events.insert(EmittedEvent {
+                                address: Address::from_slice(&address.to_be_bytes::<32>()[12..32]),
+                                topics: log.topics.len(),
+                                signature: log.topics.first().and_then(FVal::as_revm_u256),
+                            });
+                        }
+                    }
+                }
                 if let Some(ref contracts) = potential_attack_state.env.precompiled_contracts {
                     precompiled_contracts
                         .lock()
@@ -390,8 +671,28 @@ impl Analysis {
                     }
                 }
 
-                self.analyze_state_for_attacks(potential_attack_state, &result);
+                if potential_attack_state.config().locked_ether {
+                    self.check_victim_balance(&potential_attack_state, &deposit, &withdrawal);
+                }
+                if report_privileges {
+                    self.record_privileged_effects(&potential_attack_state, &privileged_effects);
+                }
+
+                self.analyze_state_for_attacks(
+                    potential_attack_state,
+                    &privileged_slots,
+                    &callable,
+                    &result,
+                );
             });
+        // relayed paths only serve the tx.origin detector and paths on zeroed storage only the
+        // initializer detector, neither seeds the next round
+        if let Some(relayed) = relayed {
+            relayed.analyze_relayed_states(&result);
+        }
+        if let Some(initializer) = initializer {
+            initializer.analyze_initializer_states(&result);
+        }
         let blocks = self.blocks;
 
         let result = result.into_inner().unwrap();
@@ -425,6 +726,30 @@ impl Analysis {
             Some(set)
         };
 
+        let emitted_events = emitted_events.into_inner().unwrap();
+        let emitted_events = if emitted_events.is_empty() {
+            None
+        } else {
+            Some(emitted_events)
+        };
+
+        let privileged_effects = if report_privileges {
+            let mut effects: Vec<PrivilegedEffect> = privileged_effects
+                .into_inner()
+                .unwrap()
+                .into_iter()
+                .map(|((selector, effect), caller_checks)| PrivilegedEffect {
+                    selector,
+                    effect,
+                    caller_checks,
+                })
+                .collect();
+            effects.sort_by_key(|e| (e.selector, e.effect));
+            Some(effects)
+        } else {
+            None
+        };
+
         let new_states = self
             .graph
             .end_states_storage()
@@ -437,7 +762,300 @@ impl Analysis {
             loaded_accounts,
             precompiled_contracts,
             blocks,
+            unsupported_exponentiation: unsupported_exponentiation.into_inner(),
+            unsupported_probes: self.probe_bound_reached.into_inner(),
+            emitted_events,
+            deposit: deposit.into_inner().unwrap(),
+            withdrawal: withdrawal.into_inner(),
+            explored_paths,
+            privileged_effects,
+        }
+    }
+
+    // Records the victim's state changing operations by selector, keeping the checks against the
+    // caller of the least guarded path
+    fn record_privileged_effects(
+        &self,
+        state: &SeState,
+        privileged_effects: &Mutex<HashMap<(Option<u32>, Effect), Vec<U256>>>,
+    ) {
+        match state.halting_reason {
+            Some(HaltingReason::Stop | HaltingReason::Return | HaltingReason::Selfdestruct) => {}
+            _ => return,
+        }
+        let effects: Vec<Effect> = state
+            .effects
+            .iter()
+            .filter(|(acc, _)| *acc == self.to)
+            .map(|(_, effect)| *effect)
+            .collect();
+        if effects.is_empty() {
+            return;
+        }
+
+        // paths which do not pin down the selector, e.g. the fallback function
+        let selector = calldata_selector(state);
+        let value = match state.get_value(&selector) {
+            Some(value) => value,
+            None => return,
+        };
+        let mut check = state.clone();
+        check.push_constraint(neql(&selector, &value));
+        let selector = if check.check_sat() {
+            None
+        } else {
+            FVal::as_usize(&value).map(|v| v as u32)
+        };
+
+        let mut caller_checks: Vec<U256> = state
+            .caller_checks
+            .iter()
+            .filter_map(FVal::as_revm_u256)
+            .collect();
+        caller_checks.sort();
+        caller_checks.dedup();
+
+        let mut privileged_effects = privileged_effects.lock().unwrap();
+        for effect in effects {
+            let checks = privileged_effects
+                .entry((selector, effect))
+                .or_insert_with(|| caller_checks.clone());
+            if caller_checks.len() < checks.len() {
+                *checks = caller_checks.clone();
+            }
+        }
+    }
+
+    // Runs the tx.origin detector on the end states of the relayed exploration
+    fn analyze_relayed_states(mut self, result: &Mutex<Vec<Attack>>) {
+        let end_states = self.end_states.take().unwrap();
+        end_states
+            .into_par_iter()
+            .for_each(|state| self.check_origin_auth(&state, result));
+    }
+
+    // Checks if a relayed call passes an origin check the attacker alone could not pass
+    fn check_origin_auth(&self, potential_attack_state: &SeState, result: &Mutex<Vec<Attack>>) {
+        if !potential_attack_state.flags.contains(Flags::ORIGIN_AUTH) {
+            return;
+        }
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            let mut direct = potential_attack_state.clone();
+            let origin = Arc::clone(&direct.input_tx().origin);
+            let attacker_addr = Arc::clone(&direct.env.get_account(&self.from).addr);
+            direct.push_constraint(eql(&origin, &attacker_addr));
+            if !direct.check_sat() {
+                info!("Privileged operation may be reachable through a phished origin");
+                self.report_attack(
+                    potential_attack_state,
+                    AttackType::TxOriginAuth,
+                    result,
+                    |data| self.verify_tx_origin_auth(potential_attack_state, data),
+                );
+            }
+        }
+    }
+
+    // Runs the initializer detector on the end states of the exploration on zeroed storage
+    fn analyze_initializer_states(mut self, result: &Mutex<Vec<Attack>>) {
+        let end_states = self.end_states.take().unwrap();
+        let privileged_slots: HashSet<BVal> = end_states
+            .iter()
+            .flat_map(|s| s.privileged_slots.iter())
+            .filter(|(acc, _)| *acc == self.to)
+            .map(|(_, slot)| Arc::clone(slot))
+            .collect();
+        end_states
+            .into_par_iter()
+            .for_each(|state| self.check_initializer(&state, &privileged_slots, result));
+    }
+
+    // Checks if the first transaction can claim the privileged slots of the freshly deployed
+    // victim, i.e. if an attacker can front-run its initializer
+    fn check_initializer(
+        &self,
+        potential_attack_state: &SeState,
+        privileged_slots: &HashSet<BVal>,
+        result: &Mutex<Vec<Attack>>,
+    ) {
+        if !std::matches!(
+            potential_attack_state.halting_reason,
+            Some(HaltingReason::Stop | HaltingReason::Return)
+        ) {
+            return;
+        }
+        let attacker_addr = &potential_attack_state.env.get_account(&self.from).addr;
+        let mut slots: Vec<BVal> = crate::se::config::EIP1967_SLOTS
+            .iter()
+            .map(|slot| const256(slot))
+            .collect();
+        for slot in potential_attack_state
+            .account()
+            .owner
+            .iter()
+            .chain(privileged_slots)
+        {
+            if !slots.contains(slot) {
+                slots.push(Arc::clone(slot));
+            }
+        }
+        for slot in slots.iter() {
+            let index = match FVal::as_revm_u256(slot) {
+                Some(index) => index,
+                None => continue,
+            };
+            let mut check = potential_attack_state.clone();
+            let claimed = and(
+                &sload(&check.memory, check.account().storage, slot),
+                &const256(crate::se::config::ADDRESS_MASK),
+            );
+            check.push_constraint(eql(&claimed, attacker_addr));
+            if !check.check_sat() {
+                continue;
+            }
+            info!("Initializer may be front-run to claim slot {:#x}", index);
+            let selector = check
+                .get_value(&calldata_selector(&check))
+                .and_then(|value| FVal::as_usize(&value))
+                .map(|value| value as u32);
+            let attack_type = AttackType::FrontRunnableInitializer {
+                selector,
+                slot: index,
+            };
+            self.report_attack(&check, attack_type, result, |data| {
+                self.verify_tx_owner(&check, data, index)
+            });
+        }
+    }
+
+    // Records whether the victim's balance can grow or shrink compared to before the transaction
+    fn check_victim_balance(
+        &self,
+        state: &SeState,
+        deposit: &Mutex<Option<Vec<TxData>>>,
+        withdrawal: &AtomicBool,
+    ) {
+        match state.halting_reason {
+            Some(HaltingReason::Selfdestruct) => {
+                withdrawal.store(true, Ordering::Relaxed);
+                return;
+            }
+            Some(HaltingReason::Stop | HaltingReason::Return) => {}
+            _ => return,
+        }
+        // the call value is credited to the victim when the transaction is created
+        let before = sub(
+            &self.graph.initial_state().account().balance,
+            &state.input_tx().callvalue,
+        );
+        let after = &state.account().balance;
+        if !withdrawal.load(Ordering::Relaxed) {
+            let mut check = state.clone();
+            check.push_constraint(lt(after, &before));
+            if check.check_sat() {
+                info!("Found a path decreasing the victim's balance");
+                withdrawal.store(true, Ordering::Relaxed);
+            }
+        }
+        if deposit.lock().unwrap().is_none() {
+            let mut check = state.clone();
+            check.push_constraint(lt(&before, after));
+            if check.check_sat() {
+                if let Some(data) = self.generate_tx_datas(&check) {
+                    deposit.lock().unwrap().get_or_insert(data);
+                }
+            }
+        }
+    }
+
+    /// Explores the probe call and returns whether any path succeeds
+    /// The sender and transaction of a successful honest call, if there is one
+    pub fn probe_round(mut self) -> Option<(Address, TxData)> {
+        assert!(self.mode.is_probe());
+        self.graph.analyze_graph();
+        self.graph
+            .end_states()
+            .iter()
+            .filter(|s| {
+                std::matches!(
+                    s.halting_reason,
+                    Some(HaltingReason::Stop | HaltingReason::Return)
+                )
+            })
+            .find_map(|s| {
+                let sender = s.get_value(&s.input_tx().caller)?;
+                let call = self.concrete_input_data_for_tx(s, &s.input_tx)?;
+                Some((convert_fval_to_address(&sender), call))
+            })
+    }
+
+    pub fn probe_reads(mut self) -> Vec<BVal> {
+        assert!(self.mode.is_probe());
+        self.graph.analyze_graph();
+        let to = self.to;
+        self.graph
+            .end_states()
+            .iter()
+            .flat_map(|s| s.mapping_reads.iter())
+            .filter(|(account, _)| *account == to)
+            .map(|(_, position)| Arc::clone(position))
+            .collect()
+    }
+
+    // A call of the selector by an honest account in a block after the state
+    fn probe(&self, state: &SeState, selector: u32) -> Analysis {
+        let mut result_state = state.as_result_state();
+        result_state.env = Arc::new(Env::from_old_env(&result_state.env));
+        Analysis::from_result_state(
+            &self.code_to_be_executed(),
+            &self.from,
+            &self.to,
+            state.config().clone(),
+            result_state,
+            AnalysisMode::Probe(selector),
+            Arc::clone(&state.memory),
+        )
+    }
+
+    // A successful call of the selector by an honest account in a block after the state
+    fn callable_after(&self, state: &SeState, selector: u32) -> Option<(Address, TxData)> {
+        self.probe(state, selector).probe_round()
+    }
+
+    // Whether an honest account can call the selector after the state, end states with the same
+    // victim storage and balance share their probe. Undecided once the bound of probes is reached.
+    fn stays_callable(&self, state: &SeState, selector: u32) -> Option<bool> {
+        let key = (
+            selector,
+            state.account().storage,
+            Arc::clone(&state.account().balance),
+        );
+        if let Some(callable) = self.probes.lock().unwrap().get(&key) {
+            return Some(*callable);
+        }
+        if self.probes.lock().unwrap().len() >= MAX_PROBES {
+            self.probe_bound_reached.store(true, Ordering::Relaxed);
+            return None;
+        }
+        let callable = self.callable_after(state, selector).is_some();
+        self.probes.lock().unwrap().insert(key, callable);
+        Some(callable)
+    }
+
+    // The mapping slots of the victim read by its balanceOf and transfer functions
+    fn balance_mappings(&self, state: &SeState) -> Vec<U256> {
+        let mut slots = vec![];
+        for selector in crate::se::config::BALANCE_SELECTORS.iter() {
+            for position in self.probe(state, *selector).probe_reads() {
+                match FVal::as_revm_u256(&position) {
+                    Some(slot) if !slots.contains(&slot) => slots.push(slot),
+                    _ => {}
+                }
+            }
         }
+        slots
     }
 
     pub fn execute_call(mut self) -> Vec<ResultState> {
@@ -453,13 +1071,19 @@ impl Analysis {
     fn analyze_state_for_attacks(
         &self,
         mut potential_attack_state: SeState,
+        privileged_slots: &HashSet<BVal>,
+        callable: &[(u32, Address, TxData)],
         result: &Mutex<Vec<Attack>>,
     ) {
         match potential_attack_state.halting_reason {
             // Check for generic INVALID or REVERT opcode invocation, which would match custom user-generated assert failures
             Some(HaltingReason::Invalid) | Some(HaltingReason::Revert) => {
                 if potential_attack_state.failed_assert {
-                    info!("A custom user-generated assert might be violated!");
+                    let attack_type = potential_attack_state
+                        .panic_code
+                        .map(AttackType::from_panic_code)
+                        .unwrap_or(AttackType::AssertFailed);
+                    info!("A custom user-generated assert might be violated: {}!", attack_type);
 
                     if let Some(data) = self.generate_tx_datas(&potential_attack_state) {
                         if self
@@ -484,8 +1108,14 @@ impl Analysis {
                                 balance: _,
                                 number: _,
                                 timestamp: _,
+                                coinbase: _,
+                                prevrandao: _,
+                                basefee: _,
+                                blob_basefee: _,
+                                blobhashes: _,
                                 input_data,
                                 storage_upd: _,
+                                origin: _,
                             } in data.iter()
                             {
                                 let input = ForgeInput {
@@ -498,8 +1128,9 @@ impl Analysis {
 
                             let attack = Attack {
                                 txs: data,
-                                attack_type: AttackType::AssertFailed,
+                                attack_type,
                                 counterexamples: Some(attack_counterexample),
+                                callback: None,
                             };
                             result.lock().unwrap().push(attack);
 
@@ -511,7 +1142,7 @@ impl Analysis {
                     } else {
                         println!(
                             "Found attack, {}, but could not generate tx data!",
-                            AttackType::AssertFailed
+                            attack_type
                         );
                     }
                 }
@@ -555,8 +1186,14 @@ impl Analysis {
                                     balance: _,
                                     number: _,
                                     timestamp: _,
+                                    coinbase: _,
+                                    prevrandao: _,
+                                    basefee: _,
+                                    blob_basefee: _,
+                                    blobhashes: _,
                                     input_data,
                                     storage_upd: _,
+                                    origin: _,
                                 } in data.iter()
                                 {
                                     let input = ForgeInput {
@@ -571,6 +1208,7 @@ impl Analysis {
                                     txs: data,
                                     attack_type: AttackType::AssertFailed,
                                     counterexamples: Some(attack_counterexample),
+                                    callback: None,
                                 };
                                 result.lock().unwrap().push(attack);
                             } else {
@@ -585,12 +1223,59 @@ impl Analysis {
             }
         };
 
+        // Check if a revert the user marked as unreachable can be triggered
+        if let Some(ref reason) = potential_attack_state.revert_reason {
+            if potential_attack_state
+                .config()
+                .forbidden_reverts
+                .contains(reason)
+            {
+                info!("Forbidden revert {} might be reachable", reason);
+                let attack_type = AttackType::ForbiddenRevert(reason.clone());
+                self.report_attack(&potential_attack_state, attack_type, result, |data| {
+                    self.verify_tx_revert_reason(&potential_attack_state, data, reason)
+                });
+            }
+        }
+
+        // Check if the victim can emit an event the user marked as forbidden
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            for signature in potential_attack_state.config().forbidden_events.iter() {
+                for log in potential_attack_state.logs[self.graph.initial_state().logs.len()..]
+                    .iter()
+                    .filter(|log| log.account == self.to && !log.topics.is_empty())
+                {
+                    let mut check = potential_attack_state.clone();
+                    check.push_constraint(eql(&log.topics[0], &const256(&signature.to_string())));
+                    if !check.check_sat() {
+                        continue;
+                    }
+                    info!("Forbidden event {:#x} might be emitted", signature);
+                    let attack_type = AttackType::ForbiddenEvent(*signature);
+                    if self.report_attack(&check, attack_type, result, |data| {
+                        self.verify_tx_event(&check, data, *signature)
+                    }) {
+                        break;
+                    }
+                }
+            }
+        }
+
         // Otherwise check for the hardcoded attack states EthBMC was originally designed for
         let initial_state = &self.graph.initial_state();
         let attacker = &self.from;
 
-        // If we know the owner variable of the victim account then check if we changed it
-        if let Some(ref index) = potential_attack_state.account().owner {
+        // Check if the attacker can change the owner variable of the victim account, either
+        // supplied by the user or inferred from comparisons against the caller
+        let mut owner_slots: Vec<&BVal> = potential_attack_state.account().owner.iter().collect();
+        for slot in privileged_slots {
+            if !owner_slots.contains(&slot) {
+                owner_slots.push(slot);
+            }
+        }
+        for index in owner_slots {
             {
                 let mut check = potential_attack_state.clone();
                 let initial_owner = sload(&check.memory, initial_state.account().storage, index);
@@ -612,6 +1297,7 @@ impl Analysis {
                                 txs: data,
                                 attack_type: AttackType::CanChangeOwner,
                                 counterexamples: None,
+                                callback: None,
                             };
                             result.lock().unwrap().push(attack);
                         }
@@ -637,6 +1323,7 @@ impl Analysis {
                         txs: data,
                         attack_type: AttackType::DeleteContract,
                         counterexamples: None,
+                        callback: None,
                     };
                     result.lock().unwrap().push(attack);
                 }
@@ -648,34 +1335,392 @@ impl Analysis {
             }
         }
 
-        // Check if we can hijack control flow
-        if potential_attack_state
-            .flags
-            .contains(Flags::HIJACK_CONTROL_FLOW)
+        // Check if the victim can emit a Transfer event without touching its storage, i.e. without
+        // any balance bookkeeping
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
         {
-            info!("Control flow hijack constraint may have been violated");
-            if let Some(data) = self.generate_tx_datas(&potential_attack_state) {
-                if self
-                    .verify_tx_hijack_control_flow(&potential_attack_state, &data)
-                    .is_some()
+            if potential_attack_state.account().storage == initial_state.account().storage
+                && potential_attack_state.account().mappings == initial_state.account().mappings
+            {
+                let transfer_topic = const256(crate::se::config::TRANSFER_TOPIC);
+                for log in potential_attack_state.logs[initial_state.logs.len()..]
+                    .iter()
+                    .filter(|log| log.account == self.to && log.topics.len() == 3)
                 {
-                    let attack = Attack {
-                        txs: data,
-                        attack_type: AttackType::HijackControlFlow,
-                        counterexamples: None,
+                    let mut check = potential_attack_state.clone();
+                    let amount = mload(&check.memory, log.mem, &log.offset);
+                    check.push_constraint(eql(&log.topics[0], &transfer_topic));
+                    check.push_constraint(neql(&amount, &zero()));
+                    if !check.check_sat() {
+                        continue;
+                    }
+                    info!("Transfer event without balance change may be emitted");
+                    if self.report_attack(&check, AttackType::FakeTransferEvent, result, |data| {
+                        self.verify_tx_fake_transfer_event(&check, data)
+                    }) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Check if unchecked arithmetic can wrap around before being stored or sent
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            for overflow in potential_attack_state.overflows.iter() {
+                let mut check = potential_attack_state.clone();
+                check.push_constraint(Arc::clone(&overflow.condition));
+                if !check.check_sat() {
+                    continue;
+                }
+                info!("Integer overflow constraint may have been violated");
+                if self.report_attack(&check, AttackType::IntegerOverflow, result, |data| {
+                    self.verify_tx_overflow(&check, data, overflow.pc)
+                }) {
+                    break;
+                }
+            }
+        }
+
+        // Check if the victim writes to a storage slot the attacker can choose
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            let critical_slots = &potential_attack_state.config().critical_slots;
+            for (_, slot) in potential_attack_state
+                .storage_writes
+                .iter()
+                .filter(|(acc, _)| *acc == self.to)
+            {
+                // mapping entries and struct fields behind them are keyed by a hash, only
+                // computed offsets (e.g. array indices) can reach other slots
+                let hashed = |v: &BVal| matches!(v, Val256::FSHA3(..));
+                match slot.val() {
+                    Val256::FSHA3(..) => continue,
+                    Val256::FAdd(a, b)
+                        if (hashed(a) && FVal::is_constant(b))
+                            || (hashed(b) && FVal::is_constant(a)) =>
+                    {
+                        continue
+                    }
+                    _ => {}
+                }
+                let mut check = potential_attack_state.clone();
+                if critical_slots.is_empty() {
+                    // the slot must not be pinned down by the path constraints
+                    let chosen = match check.get_value(slot) {
+                        Some(v) => v,
+                        None => continue,
                     };
-                    result.lock().unwrap().push(attack);
+                    check.push_constraint(neql(slot, &chosen));
+                    if !check.check_sat() {
+                        continue;
+                    }
+                } else {
+                    let reachable = critical_slots
+                        .iter()
+                        .map(|critical| const256(&critical.to_string()))
+                        .find(|critical| {
+                            let mut c = check.clone();
+                            c.push_constraint(eql(slot, critical));
+                            c.check_sat()
+                        });
+                    match reachable {
+                        Some(critical) => check.push_constraint(eql(slot, &critical)),
+                        None => continue,
+                    }
+                }
+                info!("Attacker controlled storage write to slot {:?}", slot);
+                let attack_type = AttackType::ArbitraryStorageWrite(format!("{:?}", slot));
+                let index = match check.get_value(slot).and_then(|v| FVal::as_revm_u256(&v)) {
+                    Some(index) => index,
+                    None => continue,
+                };
+                check.push_constraint(eql(slot, &const256(&index.to_string())));
+                if self.report_attack(&check, attack_type, result, |data| {
+                    self.verify_tx_owner(&check, data, index)
+                }) {
+                    break;
                 }
-            } else {
-                debug!(
-                    "Found attack, {}, but could not generate tx data!",
-                    AttackType::HijackControlFlow
-                );
             }
         }
 
-        // Check if we can steal money
-        let balance = Arc::clone(&potential_attack_state.env.get_account(attacker).balance);
+        // Check if the victim persists its changes after an external call failed unchecked
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            if potential_attack_state.account().storage != initial_state.account().storage
+                || potential_attack_state.account().mappings != initial_state.account().mappings
+            {
+                for call in potential_attack_state.failed_calls.iter() {
+                    info!("Failed call at pc {:#x} might be unchecked", call.pc);
+                    let callee = convert_fval_to_address(
+                        &potential_attack_state.env.get_account(&call.callee).addr,
+                    );
+                    if let Some(data) = self.generate_tx_datas(&potential_attack_state) {
+                        if let Some(delta) = self.verify_tx_unchecked_call(
+                            &potential_attack_state,
+                            &data,
+                            call.pc,
+                            callee,
+                        ) {
+                            let attack = Attack {
+                                txs: data,
+                                attack_type: AttackType::UncheckedCall {
+                                    pc: call.pc,
+                                    callee,
+                                    delta,
+                                },
+                                counterexamples: None,
+                                callback: self
+                                    .generate_callback_data(&potential_attack_state)
+                                    .map(|(_, callback)| callback),
+                            };
+                            result.lock().unwrap().push(attack);
+                        }
+                    } else {
+                        debug!(
+                            "Found attack, unchecked call at pc {:#x}, but could not generate tx data!",
+                            call.pc
+                        );
+                    }
+                }
+            }
+        }
+
+        // Check if ether transfers or privileged writes depend on predictable block values
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            for effect in potential_attack_state.block_dependence.iter() {
+                let mut check = potential_attack_state.clone();
+                let (attack_type, pc, operand) = match effect {
+                    BlockDependentEffect::Transfer { pc, value } => {
+                        check.push_constraint(neql(value, &zero()));
+                        if !check.check_sat() {
+                            continue;
+                        }
+                        // pin the transferred value so the replay is compared against it
+                        let amount =
+                            match check.get_value(value).and_then(|v| FVal::as_revm_u256(&v)) {
+                                Some(amount) => amount,
+                                None => continue,
+                            };
+                        check.push_constraint(eql(value, &const256(&amount.to_string())));
+                        (AttackType::BlockDependentTransfer(*pc), *pc, amount)
+                    }
+                    BlockDependentEffect::Store { pc, slot } => {
+                        let privileged = privileged_slots.contains(slot)
+                            || check.account().owner.as_ref() == Some(slot);
+                        match FVal::as_revm_u256(slot) {
+                            Some(index) if privileged => {
+                                (AttackType::BlockDependentStore(*pc), *pc, index)
+                            }
+                            _ => continue,
+                        }
+                    }
+                };
+                if !check.check_sat() {
+                    continue;
+                }
+                info!("Block dependence constraint may have been violated");
+                self.report_attack(&check, attack_type, result, |data| {
+                    self.verify_tx_block_dependence(&check, data, pc, operand)
+                });
+            }
+        }
+
+        // Check if a delegate call can overwrite the pointer slots of the proxy
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            for collision in potential_attack_state.storage_collisions.iter() {
+                let index = match FVal::as_revm_u256(&collision.slot) {
+                    Some(index) => index,
+                    None => continue,
+                };
+                let mut check = potential_attack_state.clone();
+                check.push_constraint(Arc::clone(&collision.overwritten));
+                if !check.check_sat() {
+                    continue;
+                }
+                info!("Delegate call may overwrite slot {:#x} of the proxy", index);
+                let attack_type = AttackType::StorageCollision {
+                    pc: collision.pc,
+                    slot: index,
+                };
+                self.report_attack(&check, attack_type, result, |data| {
+                    self.verify_tx_storage_collision(&check, data, index)
+                });
+            }
+        }
+
+        // Check if we can hijack control flow
+        if potential_attack_state
+            .flags
+            .contains(Flags::HIJACK_CONTROL_FLOW)
+        {
+            info!("Control flow hijack constraint may have been violated");
+            if let Some(data) = self.generate_tx_datas(&potential_attack_state) {
+                if self
+                    .verify_tx_hijack_control_flow(&potential_attack_state, &data)
+                    .is_some()
+                {
+                    let attack = Attack {
+                        txs: data,
+                        attack_type: AttackType::HijackControlFlow,
+                        counterexamples: None,
+                        callback: None,
+                    };
+                    result.lock().unwrap().push(attack);
+                }
+            } else {
+                debug!(
+                    "Found attack, {}, but could not generate tx data!",
+                    AttackType::HijackControlFlow
+                );
+            }
+        }
+
+        // Check if re-entering the victim lets the attacker controlled accounts gain funds
+        // which the victim paid out while it was re-entered
+        if potential_attack_state.flags.contains(Flags::REENTRANCY) {
+            let mut check = potential_attack_state.clone();
+            let hijack = *check
+                .env
+                .try_get_account_id_by_addr(&const256(crate::se::config::HIJACK_ADDR))
+                .unwrap();
+            let initial_attacker_balance = initial_state
+                .env
+                .get_account(attacker)
+                .initial_attacker_balance
+                .as_ref()
+                .unwrap();
+            let initial_funds = add(
+                initial_attacker_balance,
+                initial_state
+                    .env
+                    .get_account(&hijack)
+                    .initial_attacker_balance
+                    .as_ref()
+                    .unwrap(),
+            );
+            let attacker_balance = &check.env.get_account(attacker).balance;
+            let funds = add(attacker_balance, &check.env.get_account(&hijack).balance);
+            let (before, after) = check.env.reentrancy_balances().cloned().unwrap();
+            let constraints = vec![
+                // neither sum of balances may wrap around
+                le(initial_attacker_balance, &initial_funds),
+                le(attacker_balance, &funds),
+                lt(&initial_funds, &funds),
+                lt(&after, &before),
+            ];
+            for constraint in constraints {
+                check.push_constraint(constraint);
+            }
+
+            if check.check_sat() {
+                info!("Reentrancy constraint may have been violated");
+                self.report_attack(&check, AttackType::Reentrancy, result, |data| {
+                    self.verify_tx_reentrancy(&check, data)
+                });
+            }
+        }
+
+        // Check if the attacker can increase their token balance without paying ether for it,
+        // tokens and ether are different units and compared separately
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            let initial_eth = Arc::clone(
+                initial_state
+                    .env
+                    .get_account(attacker)
+                    .initial_attacker_balance
+                    .as_ref()
+                    .unwrap(),
+            );
+            let attacker_account = potential_attack_state.env.get_account(attacker);
+            let final_eth = Arc::clone(&attacker_account.balance);
+            let holder = FVal::as_revm_u256(&attacker_account.addr).unwrap();
+            for slot in self.balance_slots.iter() {
+                let entry = const256(&mapping_entry(holder, *slot).to_string());
+                let mut check = potential_attack_state.clone();
+                let initial_tokens = sload(&check.memory, initial_state.account().storage, &entry);
+                let final_tokens = sload(&check.memory, check.account().storage, &entry);
+                check.push_constraint(lt(&initial_tokens, &final_tokens));
+                check.push_constraint(le(&initial_eth, &final_eth));
+                if !check.check_sat() {
+                    continue;
+                }
+                info!("Token theft constraint may have been violated");
+                let attack_type = AttackType::TokenTheft(*slot);
+                if self.report_attack(&check, attack_type, result, |data| {
+                    self.verify_tx_token_theft(&check, data)
+                }) {
+                    break;
+                }
+            }
+        }
+
+        // Check if the attacker can make a function revert for everyone else
+        if let Some(HaltingReason::Stop | HaltingReason::Return) =
+            potential_attack_state.halting_reason
+        {
+            for (selector, sender, call) in callable {
+                if self.stays_callable(&potential_attack_state, *selector) != Some(false) {
+                    continue;
+                }
+                info!("Function {:#010x} may no longer be callable", selector);
+                let attack_type = AttackType::DenialOfService(*selector);
+                self.report_attack(&potential_attack_state, attack_type, result, |data| {
+                    self.verify_tx_denial_of_service(&potential_attack_state, data, *sender, call)
+                });
+            }
+        }
+
+        // Check if a signature accepted in an earlier transaction can be used again. The earlier
+        // signature is pinned to a known one for the replay, since it cannot be forged.
+        let changes_state = std::matches!(
+            potential_attack_state.halting_reason,
+            Some(HaltingReason::Stop | HaltingReason::Return)
+        ) && potential_attack_state
+            .effects
+            .iter()
+            .any(|(id, _)| *id == self.to);
+        if changes_state {
+            let (earlier, current) = potential_attack_state
+                .ecrecovers
+                .split_at(initial_state.ecrecovers.len());
+            for first in earlier {
+                for second in current {
+                    for (attack_type, reused) in [
+                        (AttackType::SignatureReplay, first.same_signature(second)),
+                        (AttackType::SignatureMalleability, first.twin_signature(second)),
+                    ] {
+                        let mut check = potential_attack_state.clone();
+                        check.push_constraint(neql(&first.signer, &zero()));
+                        check.push_constraint(reused);
+                        if !check.config().no_verify {
+                            check.push_constraint(first.known_signature());
+                        }
+                        if !check.check_sat() {
+                            continue;
+                        }
+                        info!("Signature may be accepted more than once");
+                        let malleated = attack_type == AttackType::SignatureMalleability;
+                        self.report_attack(&check, attack_type, result, |data| {
+                            self.verify_tx_signature_replay(&check, data, malleated)
+                        });
+                    }
+                }
+            }
+        }
+
+        // Check if we can steal money
+        let balance = Arc::clone(&potential_attack_state.env.get_account(attacker).balance);
         potential_attack_state.push_constraint(lt(
             &Arc::clone(
                 initial_state
@@ -698,6 +1743,7 @@ impl Analysis {
                         txs: data,
                         attack_type: AttackType::StealMoney,
                         counterexamples: None,
+                        callback: None,
                     };
                     result.lock().unwrap().push(attack);
                 } else {
@@ -717,10 +1763,11 @@ impl Analysis {
         initial_tx: &TxId,
         victim: &AccountId,
         init_state: Option<ResultState>,
+        probe: Option<u32>,
         context: Arc<Context>,
         memory: Arc<SymbolicMemory>,
     ) -> SymbolicGraph {
-        let state = match init_state {
+        let mut state = match init_state {
             Some(s) => SeState::from_result_state(
                 s,
                 Arc::clone(&context),
@@ -734,16 +1781,10 @@ impl Analysis {
                     SeState::new(Arc::clone(&context), memory, &env, *victim, *initial_tx);
 
                 // Restricting the analysis to "*prove*" functions
-                // Loading the calldata from memory
-                let load = mload(&new_state.memory, new_state.input_tx().data, &const256("0"));
-
                 if let Some(selector) = env.get_selector() {
                     let prove_selector =
                         const_vec(&hexdecode::decode(selector.as_bytes()).unwrap());
-                    // Selecting the first 4 bytes from the calldata
-                    let shiftval = const_u256(OldU256::from(224));
-                    let shiftop = lshr(&load, &shiftval);
-                    let prove_constraint = eql(&prove_selector, &shiftop);
+                    let prove_constraint = eql(&prove_selector, &calldata_selector(&new_state));
                     // The calldata's selector should be pointing to the "prove" function
                     // Adding the constraint to the state
                     new_state.push_constraint(prove_constraint);
@@ -751,9 +1792,48 @@ impl Analysis {
                 new_state
             },
         };
+        if let Some(selector) = probe {
+            let selector = const_usize(selector as usize);
+            state.push_constraint(eql(&selector, &calldata_selector(&state)));
+        }
         SymbolicGraph::new(state)
     }
 
+    /// Generates the transactions leading to `state` and reports the attack if `verify` accepts
+    /// them, returns whether the attack was reported
+    fn report_attack<F>(
+        &self,
+        state: &SeState,
+        attack_type: AttackType,
+        result: &Mutex<Vec<Attack>>,
+        verify: F,
+    ) -> bool
+    where
+        F: FnOnce(&[TxData]) -> Option<()>,
+    {
+        let data = match self.generate_tx_datas(state) {
+            Some(data) => data,
+            None => {
+                debug!(
+                    "Found attack, {}, but could not generate tx data!",
+                    attack_type
+                );
+                return false;
+            }
+        };
+        if verify(&data).is_none() {
+            return false;
+        }
+        let attack = Attack {
+            txs: data,
+            attack_type,
+            counterexamples: None,
+            callback: self.generate_callback_data(state).map(|(_, callback)| callback),
+        };
+        result.lock().unwrap().push(attack);
+        true
+    }
+
     fn generate_tx_datas(&self, state: &SeState) -> Option<Vec<TxData>> {
         if let Some(data) = self.generate_tx_data(state) {
             return Some(data);
@@ -771,11 +1851,23 @@ impl Analysis {
         Some(attack_data)
     }
 
+    /// The account the hijack account re-enters and the call it re-enters with, if `state` did
+    fn generate_callback_data(&self, state: &SeState) -> Option<(Address, TxData)> {
+        let tx = state.env.reentrancy_txs().first()?;
+        let receiver = convert_fval_to_address(&state.env.get_tx(tx).addr);
+        Some((receiver, self.concrete_input_data_for_tx(state, tx)?))
+    }
+
     fn execute_concrete_evm(
         &self,
         state: &SeState,
         attack_data: &[TxData],
     ) -> Option<evmexec::evm::EvmResult> {
+        let mut evm = self.setup_concrete_evm(state, attack_data)?;
+        self.replay_on_concrete_evm(&mut evm, state, attack_data)
+    }
+
+    fn setup_concrete_evm(&self, state: &SeState, attack_data: &[TxData]) -> Option<Evm> {
         let mut genesis: Genesis = (*state.env).clone().into();
 
         // Updating geth genesis w/ counterexample generated values
@@ -788,27 +1880,67 @@ impl Analysis {
 
         let mut evm: Evm = Evm::new(genesis);
         evm.update_state_from_genesis();
+
+        // the concrete hijack account re-enters the same way the symbolic one did
+        if let Some((receiver, callback)) = self.generate_callback_data(state) {
+            let hijack = Address::from_str(crate::se::config::HIJACK_ADDR_HEX).unwrap();
+            let info = evm.db.load_account(hijack).ok()?.info.clone();
+            let code = Bytecode::new_raw(hijack_code(receiver, &callback, true));
+            evm.db.insert_account_info(hijack, info.with_code(code));
+        }
+        Some(evm)
+    }
+
+    fn replay_on_concrete_evm(
+        &self,
+        evm: &mut Evm,
+        state: &SeState,
+        attack_data: &[TxData],
+    ) -> Option<evmexec::evm::EvmResult> {
         let sender = convert_fval_to_address(&state.env.get_account(&self.from).addr);
         let receiver = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let hijack = Address::from_str(crate::se::config::HIJACK_ADDR_HEX).unwrap();
 
         let mut execution;
-        for (
-            i,
-            TxData {
+        for (i, tx) in attack_data.iter().enumerate() {
+            let TxData {
                 balance,
-                number: _,
-                timestamp: _,
+                number,
+                timestamp,
+                coinbase,
+                prevrandao,
+                basefee,
+                blob_basefee,
+                blobhashes,
                 input_data,
                 storage_upd: _,
-            },
-        ) in attack_data.iter().enumerate()
-        {
+                origin,
+            } = tx;
+            // relayed calls are sent by their origin to the hijack account, which forwards them
+            let (tx_sender, tx_receiver, value, input_data) = match origin {
+                Some(origin) => {
+                    let info = evm.db.load_account(hijack).ok()?.info.clone();
+                    let code = Bytecode::new_raw(hijack_code(receiver, tx, false));
+                    evm.db.insert_account_info(hijack, info.with_code(code));
+                    (*origin, hijack, U256::ZERO, vec![])
+                }
+                None => (sender, receiver, *balance, input_data.clone()),
+            };
             let input = EvmInput {
-                input_data: convert_data_to_bytes(input_data.clone()),
-                sender: sender.clone(),
-                receiver: receiver.clone(),
+                input_data: convert_data_to_bytes(input_data),
+                sender: tx_sender,
+                receiver: tx_receiver,
                 gas: 100_000_000,
-                value: revm::primitives::U256::from(*balance),
+                value,
+                block: BlockInput {
+                    number: Some(*number),
+                    timestamp: Some(*timestamp),
+                    coinbase: Some(*coinbase),
+                    prevrandao: Some(*prevrandao),
+                    basefee: Some(*basefee),
+                    blob_basefee: Some(*blob_basefee),
+                    blobhashes: blobhashes.clone(),
+                },
             };
             execution = evm.execute(input);
 
@@ -840,6 +1972,71 @@ impl Analysis {
         }
     }
 
+    fn verify_tx_token_theft(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let sender = convert_fval_to_address(&state.env.get_account(&self.from).addr);
+        let receiver = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let mut evm = self.setup_concrete_evm(state, attack_data)?;
+        let initial_tokens = evm.balance_of(receiver, sender)?;
+        let initial_eth = evm.db.load_account(sender).ok()?.info.balance;
+        self.replay_on_concrete_evm(&mut evm, state, attack_data)?;
+        let final_tokens = evm.balance_of(receiver, sender)?;
+        let final_eth = evm.db.load_account(sender).ok()?.info.balance;
+        if initial_tokens < final_tokens && initial_eth <= final_eth {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn verify_tx_denial_of_service(
+        &self,
+        state: &SeState,
+        attack_data: &[TxData],
+        sender: Address,
+        call: &TxData,
+    ) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        // the honest call found by the probe succeeds before the last attacker transaction
+        let receiver = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let honest_call = EvmInput {
+            input_data: convert_data_to_bytes(call.input_data.clone()),
+            sender,
+            receiver,
+            gas: 100_000_000,
+            value: U256::ZERO,
+            block: BlockInput {
+                number: Some(call.number),
+                timestamp: Some(call.timestamp),
+                coinbase: Some(call.coinbase),
+                prevrandao: Some(call.prevrandao),
+                basefee: Some(call.basefee),
+                blob_basefee: Some(call.blob_basefee),
+                blobhashes: call.blobhashes.clone(),
+            },
+        };
+
+        let prefix = &attack_data[..attack_data.len() - 1];
+        let mut before = self.setup_concrete_evm(state, attack_data)?;
+        if !prefix.is_empty() {
+            self.replay_on_concrete_evm(&mut before, state, prefix)?;
+        }
+        before.execute(honest_call.clone()).ok()?.result.output?;
+
+        let mut evm = self.setup_concrete_evm(state, attack_data)?;
+        self.replay_on_concrete_evm(&mut evm, state, attack_data)?;
+        match evm.execute(honest_call).ok()?.result.output {
+            Some(_) => None,
+            None => Some(()),
+        }
+    }
+
     fn verify_tx_hijack_control_flow(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
         if state.context.config().no_verify {
             return Some(());
@@ -861,6 +2058,237 @@ impl Analysis {
         None
     }
 
+    fn verify_tx_reentrancy(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let sender = convert_fval_to_address(&state.env.get_account(&self.from).addr);
+        let hijack = Address::from_str(crate::se::config::HIJACK_ADDR_HEX).unwrap();
+        let (victim, _) = self.generate_callback_data(state)?;
+        let mut evm = self.setup_concrete_evm(state, attack_data)?;
+        let initial_funds = evm
+            .db
+            .load_account(sender)
+            .ok()?
+            .info
+            .balance
+            .saturating_add(evm.db.load_account(hijack).ok()?.info.balance);
+        let result = self.replay_on_concrete_evm(&mut evm, state, attack_data)?;
+        let final_funds = evm
+            .db
+            .load_account(sender)
+            .ok()?
+            .info
+            .balance
+            .saturating_add(evm.db.load_account(hijack).ok()?.info.balance);
+
+        // the hijack account has to actually call back into the victim
+        let reentered = result.result.trace.iter().any(|ins| match ins.instruction {
+            Instruction::Call { receiver, .. } => *ins.executed_on == hijack && receiver == victim,
+            _ => false,
+        });
+        if reentered && initial_funds < final_funds {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn verify_tx_overflow(&self, state: &SeState, attack_data: &[TxData], pc: usize) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        // the flagged SSTORE or CALL has to store or send a wrapped result of the same account
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        let mut wrapped = HashSet::new();
+        for ins in evm.result.trace {
+            if *ins.executed_on != victim {
+                continue;
+            }
+            match ins.instruction {
+                Instruction::Overflow { result, .. } => {
+                    wrapped.insert(result);
+                }
+                Instruction::SStore { value, .. } | Instruction::Call { value, .. }
+                    if ins.pc == pc && wrapped.contains(&value) =>
+                {
+                    return Some(());
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Replays the attack with a callee that reverts if it has no code of its own and collects
+    /// the storage changes the victim persists after the call at `pc` failed
+    fn verify_tx_unchecked_call(
+        &self,
+        state: &SeState,
+        attack_data: &[TxData],
+        pc: usize,
+        callee: Address,
+    ) -> Option<Vec<(U256, U256)>> {
+        if state.context.config().no_verify {
+            return Some(vec![]);
+        }
+
+        let sender = convert_fval_to_address(&state.env.get_account(&self.from).addr);
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let mut evm = self.setup_concrete_evm(state, attack_data)?;
+        let info = evm.db.load_account(callee).ok()?.info.clone();
+        if callee != sender && info.is_empty_code_hash() {
+            // revert(0, 0)
+            let code = Bytecode::new_raw(Bytes::from(vec![0x60, 0x00, 0x80, 0xfd]));
+            evm.db.insert_account_info(callee, info.with_code(code));
+        }
+        let result = self.replay_on_concrete_evm(&mut evm, state, attack_data)?;
+        result.result.output.as_ref()?;
+
+        let mut called = false;
+        let mut failed = false;
+        let mut delta = vec![];
+        for ins in result.result.trace {
+            if *ins.executed_on == callee && called {
+                if let Instruction::Revert { .. } | Instruction::Invalid {} = ins.instruction {
+                    failed = true;
+                }
+                continue;
+            }
+            if *ins.executed_on != victim {
+                continue;
+            }
+            match ins.instruction {
+                Instruction::Call { receiver, .. } if receiver == callee && ins.pc == pc => {
+                    called = true;
+                }
+                Instruction::SStore { addr, value } if failed => delta.push((addr, value)),
+                _ => {}
+            }
+        }
+        if !delta.is_empty() {
+            Some(delta)
+        } else {
+            None
+        }
+    }
+
+    /// Replays the attack with the last transaction relayed through the hijack account and checks
+    /// one of the operations guarded by the origin check executes in the victim
+    fn verify_tx_origin_auth(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let guarded: HashSet<usize> = state
+            .origin_guarded_ops
+            .iter()
+            .filter(|(account, _)| *account == self.to)
+            .map(|(_, pc)| *pc)
+            .collect();
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        for ins in evm.result.trace {
+            if *ins.executed_on != victim || !guarded.contains(&ins.pc) {
+                continue;
+            }
+            match ins.instruction {
+                Instruction::SStore { .. }
+                | Instruction::Call { .. }
+                | Instruction::Selfdestruct { .. } => return Some(()),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Replays the transactions one by one and checks an earlier one and the last one both pass
+    /// a signature of the same signer to ecrecover before writing storage, the very same
+    /// signature or with `malleated` its twin
+    fn verify_tx_signature_replay(
+        &self,
+        state: &SeState,
+        attack_data: &[TxData],
+        malleated: bool,
+    ) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let mut evm = self.setup_concrete_evm(state, attack_data)?;
+        let mut accepted = Vec::with_capacity(attack_data.len());
+        for tx in attack_data {
+            let result = self.replay_on_concrete_evm(&mut evm, state, std::slice::from_ref(tx))?;
+            accepted.push(accepted_signatures(&result, victim));
+        }
+        let (current, earlier) = accepted.split_last()?;
+        for first in earlier.iter().flatten() {
+            for second in current {
+                if reused_signature(first, second, malleated) {
+                    return Some(());
+                }
+            }
+        }
+        None
+    }
+
+    /// Replays with the block fields of the counterexample and checks the victim's operation at
+    /// `pc` writes the same slot or transfers the same value, given as `operand`
+    fn verify_tx_block_dependence(
+        &self,
+        state: &SeState,
+        attack_data: &[TxData],
+        pc: usize,
+        operand: U256,
+    ) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        for ins in evm.result.trace {
+            if *ins.executed_on != victim || ins.pc != pc {
+                continue;
+            }
+            match ins.instruction {
+                Instruction::SStore { addr, .. } if addr == operand => return Some(()),
+                Instruction::Call { value, .. } if value == operand => return Some(()),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn verify_tx_storage_collision(
+        &self,
+        state: &SeState,
+        attack_data: &[TxData],
+        index: U256,
+    ) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        for ins in evm.result.trace {
+            if *ins.executed_on != victim {
+                continue;
+            }
+            if let Instruction::SStore { addr, .. } = ins.instruction {
+                if addr == index {
+                    return Some(());
+                }
+            }
+        }
+        None
+    }
+
     fn verify_tx_suicide(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
         if state.context.config().no_verify {
             return Some(());
@@ -891,6 +2319,39 @@ impl Analysis {
         None
     }
 
+    fn verify_tx_fake_transfer_event(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let transfer_topic = U256::from_str(crate::se::config::TRANSFER_TOPIC).unwrap();
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        let mut emitted = false;
+        for ins in evm.result.trace {
+            if *ins.executed_on != victim {
+                continue;
+            }
+            match ins.instruction {
+                Instruction::SStore { .. } => return None,
+                Instruction::Log { topics, data } => {
+                    if topics.len() == 3
+                        && topics[0] == transfer_topic
+                        && data.iter().take(32).any(|b| *b != 0)
+                    {
+                        emitted = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if emitted {
+            Some(())
+        } else {
+            None
+        }
+    }
+
     fn verify_tx_assert(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
         if state.context.config().no_verify {
             return Some(());
@@ -900,11 +2361,11 @@ impl Analysis {
         for ins in evm.result.trace {
             match ins.instruction {
                 Instruction::Revert { panic, .. } => {
-                    if panic == U256::from(0x01) {
+                    if panic.is_some() && state.panic_code.map(U256::from) == panic {
                         return Some(());
                     }
                 }
-                Instruction::Invalid {} => return Some(()),
+                Instruction::Invalid {} if state.panic_code.is_none() => return Some(()),
                 Instruction::SStore { addr, value } => {
                     if addr == U256::from(0) && value == U256::from(256) {
                         return Some(());
@@ -916,6 +2377,48 @@ impl Analysis {
         None
     }
 
+    fn verify_tx_revert_reason(
+        &self,
+        state: &SeState,
+        attack_data: &[TxData],
+        reason: &RevertReason,
+    ) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        for ins in evm.result.trace {
+            if let Instruction::Revert { ref data, .. } = ins.instruction {
+                if *ins.executed_on == victim
+                    && RevertReason::decode(data.len(), |i| data.get(i).copied()).as_ref()
+                        == Some(reason)
+                {
+                    return Some(());
+                }
+            }
+        }
+        None
+    }
+
+    fn verify_tx_event(&self, state: &SeState, attack_data: &[TxData], signature: U256) -> Option<()> {
+        if state.context.config().no_verify {
+            return Some(());
+        }
+
+        let victim = convert_fval_to_address(&state.env.get_account(&self.to).addr);
+        let evm = self.execute_concrete_evm(state, attack_data)?;
+        for ins in evm.result.trace {
+            if let Instruction::Log { ref topics, .. } = ins.instruction {
+                if *ins.executed_on == victim && topics.first() == Some(&signature) {
+                    return Some(());
+                }
+            }
+        }
+        None
+    }
+
     fn verify_tx_forge_test(&self, state: &SeState, attack_data: &[TxData]) -> Option<()> {
         if state.context.config().no_verify {
             return Some(());
@@ -949,6 +2452,18 @@ impl Analysis {
         let balance = load_state.get_value(&load_state.env.get_tx(tx).callvalue)?;
         let timestamp = load_state.get_value(&load_state.env.latest_block().timestamp)?;
         let number = load_state.get_value(&load_state.env.latest_block().number)?;
+        let coinbase = load_state.get_value(&load_state.env.latest_block().coinbase)?;
+        let prevrandao = load_state.get_value(&load_state.env.latest_block().prevrandao)?;
+        let basefee = load_state.get_value(&load_state.env.latest_block().basefee)?;
+        let blob_basefee = load_state.get_value(&load_state.env.latest_block().blob_basefee)?;
+        let mut blobhashes = vec![];
+        for blobhash in load_state
+            .env
+            .latest_block()
+            .carried_blobhashes(s.config().blob_count)
+        {
+            blobhashes.push(load_state.get_value(blobhash)?);
+        }
 
         let mut storage_updates: Vec<StorageUpdate> = Vec::new();
 
@@ -985,14 +2500,9 @@ impl Analysis {
                                     let key = FVal::as_revm_u256(&index).unwrap();
                                     let slot = FVal::as_revm_u256(&mapping_key.unwrap()).unwrap();
 
-                                    // Get the location of the mapping entry as `keccak256(abi.encode(key, slot))`
-                                    let hashed_location = tiny_keccak::keccak256(
-                                        &convert_data_to_bytes(vec![key, slot]).0
-                                    );
-
                                     let record = StorageUpdate {
                                         account: account_addr,
-                                        addr: U256::from_be_bytes(hashed_location),
+                                        addr: mapping_entry(key, slot),
                                         value: FVal::as_revm_u256(&value).unwrap(),
                                     };
 
@@ -1014,7 +2524,28 @@ impl Analysis {
             }
         }
 
-        tx_data_from_bval_vec(balance, number, timestamp, res, storage_updates)
+        let origin = {
+            let tx = load_state.env.get_tx(tx);
+            if tx.origin != tx.caller {
+                Some(load_state.get_value(&tx.origin)?)
+            } else {
+                None
+            }
+        };
+
+        tx_data_from_bval_vec(
+            balance,
+            number,
+            timestamp,
+            coinbase,
+            prevrandao,
+            basefee,
+            blob_basefee,
+            blobhashes,
+            res,
+            storage_updates,
+            origin,
+        )
     }
 
     pub fn dump_debug_graph(mut self) {
@@ -1036,6 +2567,11 @@ impl Analysis {
         self.to
     }
 
+    /// The token balance mappings, either supplied or inferred once for the first transaction
+    pub fn balance_slots(&self) -> &[U256] {
+        &self.balance_slots
+    }
+
     /// This is the initial code executed, this might be different from all the code executed
     pub fn code_to_be_executed(&self) -> Vec<u8> {
         self.graph
@@ -1054,6 +2590,19 @@ pub struct ExplorationResult {
     pub loaded_accounts: Option<HashSet<LoadedAccount>>,
     pub precompiled_contracts: Option<HashSet<PrecompiledContracts>>,
     pub blocks: Option<Vec<usize>>,
+    /// Some path exceeded the exponent bound and was overapproximated
+    pub unsupported_exponentiation: bool,
+    /// Some function was not probed for denial of service since the bound of probes was reached
+    pub unsupported_probes: bool,
+    pub emitted_events: Option<HashSet<EmittedEvent>>,
+    /// Transactions increasing the victim's balance, only searched for locked ether
+    pub deposit: Option<Vec<TxData>>,
+    /// Some path decreased the victim's balance or self-destructed it
+    pub withdrawal: bool,
+    pub explored_paths: usize,
+    /// State changing operations by selector of the first transaction, with the checks against
+    /// the caller guarding them
+    pub privileged_effects: Option<Vec<PrivilegedEffect>>,
 }
 
 impl ExplorationResult {
@@ -1070,11 +2619,41 @@ impl ExplorationResult {
     }
 }
 
+/// A state changing operation reachable through a function selector
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct PrivilegedEffect {
+    /// None for paths accepting any selector, e.g. the fallback function
+    pub selector: Option<u32>,
+    pub effect: Effect,
+    /// Storage slots the caller is compared against, empty if anyone can trigger the effect
+    pub caller_checks: Vec<U256>,
+}
+
+impl fmt::Display for PrivilegedEffect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.selector {
+            Some(selector) => write!(f, "{:#010x}", selector)?,
+            None => write!(f, "fallback")?,
+        }
+        write!(f, " -> {} -> ", self.effect)?;
+        if self.caller_checks.is_empty() {
+            return write!(f, "any caller");
+        }
+        write!(f, "caller checked against")?;
+        for slot in self.caller_checks.iter() {
+            write!(f, " storage[{:#x}]", slot)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct Attack {
     pub txs: Vec<TxData>,
     pub attack_type: AttackType,
     pub counterexamples: Option<Vec<ForgeInput>>,
+    /// The call the hijack account re-enters the victim with during the attack
+    pub callback: Option<TxData>,
 }
 
 impl fmt::Display for Attack {
@@ -1095,6 +2674,13 @@ impl fmt::Display for Attack {
                 "========================================================="
             )?;
         }
+        if let Some(ref callback) = self.callback {
+            writeln!(f, "\nDumping hijack callback:\n{}", callback)?;
+            writeln!(
+                f,
+                "========================================================="
+            )?;
+        }
         Ok(())
     }
 }
@@ -1111,8 +2697,16 @@ pub struct TxData {
     pub balance: U256,
     pub number: U256,
     pub timestamp: U256,
+    pub coinbase: Address,
+    pub prevrandao: U256,
+    pub basefee: U256,
+    pub blob_basefee: U256,
+    /// Versioned hashes of the blobs the transaction carries
+    pub blobhashes: Vec<U256>,
     pub input_data: Vec<U256>,
     pub storage_upd: Vec<StorageUpdate>,
+    /// The account signing the transaction if it is not the attacker, e.g. for relayed calls
+    pub origin: Option<Address>,
 }
 
 fn convert_data_to_bytes(data: Vec<U256>) -> Bytes {
@@ -1123,16 +2717,129 @@ fn convert_data_to_bytes(data: Vec<U256>) -> Bytes {
     Bytes::from_str(&s).unwrap()
 }
 
+// Code for the hijack account which calls `receiver` with the calldata and value of `call`, with
+// `once` set it only does so the first time it is called and later calls simply stop
+fn hijack_code(receiver: Address, call: &TxData, once: bool) -> Bytes {
+    let data = convert_data_to_bytes(call.input_data.clone());
+    let len = (data.len() as u16).to_be_bytes();
+    let mut code = vec![];
+    if once {
+        code.extend_from_slice(&[
+            0x60, 0x00, 0x54, // sload(0)
+            0x61, 0x00, 0x57, 0x57, // jumpi(stop)
+            0x60, 0x01, 0x60, 0x00, 0x55, // sstore(0, 1)
+        ]);
+    }
+    let offset = (code.len() as u16 + 0x4d).to_be_bytes();
+    code.extend_from_slice(&[
+        0x61, len[0], len[1], 0x61, offset[0], offset[1], 0x60, 0x00, 0x39, // codecopy(0, data, len)
+        0x60, 0x00, 0x60, 0x00, 0x61, len[0], len[1], 0x60, 0x00, // out_size, out_off, in_size, in_off
+        0x7f, // value
+    ]);
+    code.extend_from_slice(&call.balance.to_be_bytes::<32>());
+    code.push(0x73); // receiver
+    code.extend_from_slice(receiver.as_slice());
+    code.extend_from_slice(&[
+        0x5a, 0xf1, 0x50, // pop(call(gas, ...))
+        0x5b, 0x00, // stop: stop
+    ]);
+    code.extend_from_slice(&data);
+    Bytes::from(code)
+}
+
+// The ecrecover inputs of the victim which a storage write follows, if the transaction succeeded
+fn accepted_signatures(result: &evmexec::evm::EvmResult, victim: Address) -> Vec<Vec<u8>> {
+    let ecrecover = Address::with_last_byte(1);
+    let mut accepted = vec![];
+    let mut recovered = vec![];
+    if result.result.output.is_none() {
+        return accepted;
+    }
+    for ins in result.result.trace.iter() {
+        if *ins.executed_on != victim {
+            continue;
+        }
+        match &ins.instruction {
+            Instruction::Call {
+                receiver, input, ..
+            }
+            | Instruction::StaticCall {
+                receiver, input, ..
+            } if *receiver == ecrecover => recovered.push(input.clone()),
+            Instruction::SStore { .. } => accepted.append(&mut recovered),
+            _ => {}
+        }
+    }
+    accepted
+}
+
+// Whether both ecrecover inputs recover the same signer from the same signature or, with
+// `malleated`, from the twin signature with the other v and the negated s
+fn reused_signature(first: &[u8], second: &[u8], malleated: bool) -> bool {
+    let signer = |input: &[u8]| {
+        revm::precompile::secp256k1::ec_recover_run(input, u64::MAX)
+            .ok()
+            .map(|output| output.bytes)
+            .filter(|signer| !signer.is_empty())
+    };
+    if first.len() != 128 || second.len() != 128 {
+        return false;
+    }
+    match (signer(first), signer(second)) {
+        (Some(a), Some(b)) if a == b => {}
+        _ => return false,
+    }
+    if !malleated {
+        return first == second;
+    }
+    let order = U256::from_str(crate::se::config::SECP256K1_ORDER).unwrap();
+    let s = U256::from_be_slice(&first[96..128]);
+    first[..32] == second[..32]
+        && first[32..64] != second[32..64]
+        && first[64..96] == second[64..96]
+        && U256::from_be_slice(&second[96..128]) == order.wrapping_sub(s)
+}
+
+// The first 4 bytes of the calldata of the state's transaction
+fn calldata_selector(state: &SeState) -> BVal {
+    let load = mload(&state.memory, state.input_tx().data, &const256("0"));
+    lshr(&load, &const_u256(OldU256::from(224)))
+}
+
+// The storage slot of a mapping entry, i.e. `keccak256(abi.encode(key, slot))`
+fn mapping_entry(key: U256, slot: U256) -> U256 {
+    U256::from_be_bytes(tiny_keccak::keccak256(&convert_data_to_bytes(vec![key, slot]).0))
+}
+
 fn tx_data_from_bval_vec(
     balance: BVal,
     number: BVal,
     timestamp: BVal,
+    coinbase: BVal,
+    prevrandao: BVal,
+    basefee: BVal,
+    blob_basefee: BVal,
+    blobhashes: Vec<BVal>,
     data: Vec<BVal>,
     storage_upd: Vec<StorageUpdate>,
+    origin: Option<BVal>,
 ) -> Option<TxData> {
     let balance = FVal::as_revm_u256(&balance)?;
     let number = FVal::as_revm_u256(&number)?;
     let timestamp = FVal::as_revm_u256(&timestamp)?;
+    let coinbase = convert_fval_to_address(&coinbase);
+    let prevrandao = FVal::as_revm_u256(&prevrandao)?;
+    let basefee = FVal::as_revm_u256(&basefee)?;
+    let blob_basefee = FVal::as_revm_u256(&blob_basefee)?;
+    // only the leading non-zero hashes are carried, see `Block::blob_constraints`
+    let mut carried = Vec::with_capacity(blobhashes.len());
+    for blobhash in blobhashes {
+        let blobhash = FVal::as_revm_u256(&blobhash)?;
+        if blobhash.is_zero() {
+            break;
+        }
+        carried.push(blobhash);
+    }
     let mut res = Vec::with_capacity(data.len());
     for val in data {
         res.push(FVal::as_revm_u256(&val)?);
@@ -1141,14 +2848,36 @@ fn tx_data_from_bval_vec(
         balance,
         number,
         timestamp,
+        coinbase,
+        prevrandao,
+        basefee,
+        blob_basefee,
+        blobhashes: carried,
         input_data: res,
         storage_upd,
+        origin: origin.as_ref().map(convert_fval_to_address),
     })
 }
 
 impl fmt::Display for TxData {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "Balance: {:x}", self.balance)?;
+        if let Some(origin) = self.origin {
+            writeln!(f, "Origin: {}", origin)?;
+        }
+        writeln!(
+            f,
+            "Block: number {:x}, timestamp {:x}, coinbase {}, prevrandao {:x}, basefee {:x}, blob basefee {:x}",
+            self.number,
+            self.timestamp,
+            self.coinbase,
+            self.prevrandao,
+            self.basefee,
+            self.blob_basefee
+        )?;
+        for (i, blobhash) in self.blobhashes.iter().enumerate() {
+            writeln!(f, "Blob {}: {:x}", i, blobhash)?;
+        }
         for (i, v) in self.input_data.iter().enumerate() {
             writeln!(f, "0x{:08X}:\t{:16x}", i * 32, v)?;
         }
@@ -1164,6 +2893,57 @@ pub enum AttackType {
     Reentrancy,
     CanChangeOwner,
     AssertFailed,
+    ArithmeticPanic,
+    DivisionByZero,
+    EnumConversion,
+    EmptyArrayPop,
+    IndexOutOfBounds,
+    MemoryAllocation,
+    Panic(usize),
+    IntegerOverflow,
+    ForbiddenRevert(RevertReason),
+    ForbiddenEvent(U256),
+    FakeTransferEvent,
+    ArbitraryStorageWrite(String),
+    TxOriginAuth,
+    BlockDependentTransfer(usize),
+    BlockDependentStore(usize),
+    TokenTheft(U256),
+    DenialOfService(u32),
+    SignatureReplay,
+    SignatureMalleability,
+    StorageCollision {
+        pc: usize,
+        slot: U256,
+    },
+    FrontRunnableInitializer {
+        selector: Option<u32>,
+        slot: U256,
+    },
+    LockedEther {
+        transactions: usize,
+        paths: usize,
+    },
+    UncheckedCall {
+        pc: usize,
+        callee: Address,
+        delta: Vec<(U256, U256)>,
+    },
+}
+
+impl AttackType {
+    pub fn from_panic_code(code: usize) -> Self {
+        match code {
+            0x01 => AttackType::AssertFailed,
+            0x11 => AttackType::ArithmeticPanic,
+            0x12 => AttackType::DivisionByZero,
+            0x21 => AttackType::EnumConversion,
+            0x31 => AttackType::EmptyArrayPop,
+            0x32 => AttackType::IndexOutOfBounds,
+            0x41 => AttackType::MemoryAllocation,
+            c => AttackType::Panic(c),
+        }
+    }
 }
 
 impl fmt::Display for AttackType {
@@ -1179,6 +2959,93 @@ impl fmt::Display for AttackType {
             AttackType::Reentrancy => write!(f, "can trigger reentrancy"),
             AttackType::CanChangeOwner => write!(f, "can change owner variable as attacker"),
             AttackType::AssertFailed => write!(f, "an assertion can be violated"),
+            AttackType::ArithmeticPanic => {
+                write!(f, "an arithmetic operation can over- or underflow")
+            }
+            AttackType::DivisionByZero => write!(f, "can divide or take the modulo by zero"),
+            AttackType::EnumConversion => {
+                write!(f, "can convert an out-of-range value into an enum")
+            }
+            AttackType::EmptyArrayPop => write!(f, "can pop from an empty array"),
+            AttackType::IndexOutOfBounds => write!(f, "can access an array out of bounds"),
+            AttackType::MemoryAllocation => write!(f, "can allocate too much memory"),
+            AttackType::Panic(code) => write!(f, "can trigger panic code {:#x}", code),
+            AttackType::ForbiddenRevert(reason) => {
+                write!(f, "can trigger the forbidden revert {}", reason)
+            }
+            AttackType::ForbiddenEvent(signature) => {
+                write!(f, "can emit the forbidden event {:#x}", signature)
+            }
+            AttackType::IntegerOverflow => {
+                write!(f, "unchecked arithmetic can wrap around before being stored or sent")
+            }
+            AttackType::FakeTransferEvent => {
+                write!(f, "can emit a Transfer event without changing any balance")
+            }
+            AttackType::ArbitraryStorageWrite(slot) => {
+                write!(f, "can write to an attacker controlled storage slot {}", slot)
+            }
+            AttackType::BlockDependentTransfer(pc) => {
+                write!(f, "the ether transfer at pc {:#x} depends on block values", pc)
+            }
+            AttackType::BlockDependentStore(pc) => {
+                write!(f, "the privileged storage write at pc {:#x} depends on block values", pc)
+            }
+            AttackType::TxOriginAuth => {
+                write!(f, "can perform privileged operations through a phished tx.origin")
+            }
+            AttackType::TokenTheft(slot) => {
+                write!(
+                    f,
+                    "can increase the attacker's token balance in the mapping at slot {:#x}",
+                    slot
+                )
+            }
+            AttackType::DenialOfService(selector) => {
+                write!(f, "can make function {:#010x} revert for every other caller", selector)
+            }
+            AttackType::SignatureReplay => {
+                write!(f, "can replay a signature accepted in an earlier transaction")
+            }
+            AttackType::SignatureMalleability => {
+                write!(f, "can reuse an accepted signature in its malleable (r, n - s) form")
+            }
+            AttackType::StorageCollision { pc, slot } => write!(
+                f,
+                "the delegate call at pc {:#x} can overwrite the proxy's pointer slot {:#x}",
+                pc, slot
+            ),
+            AttackType::FrontRunnableInitializer { selector, slot } => {
+                match selector {
+                    Some(selector) => write!(f, "can call initializer {:#010x}", selector)?,
+                    None => write!(f, "can call the fallback function")?,
+                }
+                write!(
+                    f,
+                    " first on the freshly deployed contract and claim privileged slot {:#x}",
+                    slot
+                )
+            }
+            AttackType::LockedEther {
+                transactions,
+                paths,
+            } => write!(
+                f,
+                "can receive ether, but none of the {} paths explored within {} transactions \
+                 transfers ether out or self-destructs",
+                paths, transactions
+            ),
+            AttackType::UncheckedCall { pc, callee, delta } => {
+                write!(
+                    f,
+                    "ignores a failed call at pc {:#x} to {} and still persists its changes",
+                    pc, callee
+                )?;
+                for (addr, value) in delta {
+                    write!(f, "\n\t{:#x}: {:#x}", addr, value)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -1232,6 +3099,113 @@ victim: 0xaad62f08b3b9f0ecc7251befbeff80c9bb488fe9
         0x3d, 0xd3, 0x00, 0x29,
     ];
 
+    // Explores the first attacker transaction against a victim with the given code
+    fn attacks_for_code(code: &str, config: SeConfig) -> Vec<Attack> {
+        let yaml = format!(
+            "
+state:
+    0xaad62f08b3b9f0ecc7251befbeff80c9bb488fe9:
+        balance: 0x100000
+        nonce: 0x1000000
+        code: {}
+
+victim: 0xaad62f08b3b9f0ecc7251befbeff80c9bb488fe9
+",
+            code
+        );
+        let yaml = &YamlLoader::load_from_str(&yaml).unwrap()[0];
+        let env = SeEnviroment::from_yaml(yaml);
+        let mut ana = Analysis::from_se_env(
+            env,
+            config,
+            Solvers::Yice {
+                count: 1,
+                timeout: 120_000,
+            },
+        );
+        ana.symbolic_round();
+        ana.exploration_result().result.unwrap_or_default()
+    }
+
+    #[test]
+    fn initializer_test() {
+        // writes the caller into the owner slot 0 unless the initialized flag in slot 1 is set
+        let yaml = "
+state:
+    0xaad62f08b3b9f0ecc7251befbeff80c9bb488fe9:
+        balance: 0x100000
+        nonce: 0x1000000
+        code: 600154600c573360005500005b600080fd
+        owner: 0x0
+        storage:
+            0x1: 0x1
+
+victim: 0xaad62f08b3b9f0ecc7251befbeff80c9bb488fe9
+";
+        let yaml = &YamlLoader::load_from_str(yaml).unwrap()[0];
+        let mut config = CONFIG.read().unwrap().clone();
+        config.initializer = true;
+        let mut ana = Analysis::from_se_env(
+            SeEnviroment::from_yaml(yaml),
+            config,
+            Solvers::Yice {
+                count: 1,
+                timeout: 120_000,
+            },
+        );
+        ana.symbolic_round();
+        let attacks = ana.exploration_result().result.unwrap_or_default();
+
+        // only the freshly deployed victim can be claimed, the deployed one is initialized
+        assert!(attacks.iter().any(|attack| std::matches!(
+            attack.attack_type,
+            AttackType::FrontRunnableInitializer { slot, .. } if slot.is_zero()
+        )));
+        assert!(attacks
+            .iter()
+            .all(|attack| attack.attack_type != AttackType::CanChangeOwner));
+    }
+
+    #[test]
+    fn probe_sender_test() {
+        let yaml = &YamlLoader::load_from_str(YAML).unwrap()[0];
+        let env = SeEnviroment::from_yaml(yaml);
+        let ana = Analysis::from_se_env(
+            env,
+            CONFIG.read().unwrap().clone(),
+            Solvers::Yice {
+                count: 1,
+                timeout: 120_000,
+            },
+        );
+        // kill()
+        let probe = ana.probe(ana.graph.initial_state(), 0x41c0_e1b5);
+        let mut state = probe.graph.initial_state().clone();
+        assert!(state.check_sat());
+
+        // the honest sender can never be the attacker
+        let caller = Arc::clone(&state.input_tx().caller);
+        let attacker = Arc::clone(&state.env.get_account(&ana.from).addr);
+        state.push_constraint(eql(&caller, &attacker));
+        assert!(!state.check_sat());
+    }
+
+    #[test]
+    fn forbidden_event_test() {
+        // PUSH2 0xdead PUSH1 0x0 PUSH1 0x0 LOG1 STOP
+        let code = "61dead60006000a100";
+        let mut config = CONFIG.read().unwrap().clone();
+        assert!(attacks_for_code(code, config.clone())
+            .iter()
+            .all(|attack| !std::matches!(attack.attack_type, AttackType::ForbiddenEvent(_))));
+
+        config.forbidden_events = vec![U256::from(0xdead)];
+        let attacks = attacks_for_code(code, config);
+        assert!(attacks
+            .iter()
+            .any(|attack| attack.attack_type == AttackType::ForbiddenEvent(U256::from(0xdead))));
+    }
+
     #[test]
     fn from_se_env_test() {
         let yaml = &YamlLoader::load_from_str(YAML).unwrap()[0];
@@ -1338,4 +3312,73 @@ victim: 0xaad62f08b3b9f0ecc7251befbeff80c9bb488fe9
             assert_eq!(ids[i] - 1, *ids[i - 1]);
         }
     }
+
+    #[test]
+    fn mapping_entry_test() {
+        assert_eq!(
+            mapping_entry(U256::from(0), U256::from(0)),
+            U256::from_str_radix(
+                "ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5",
+                16
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn reused_signature_test() {
+        let word = |value: U256| value.to_be_bytes::<32>().to_vec();
+        let [hash, v, r, s, _] =
+            crate::se::config::KNOWN_SIGNATURE.map(|value| U256::from_str(value).unwrap());
+        let order = U256::from_str(crate::se::config::SECP256K1_ORDER).unwrap();
+        let signature = [word(hash), word(v), word(r), word(s)].concat();
+        let twin = [
+            word(hash),
+            word(U256::from(55) - v),
+            word(r),
+            word(order - s),
+        ]
+        .concat();
+
+        assert!(reused_signature(&signature, &signature, false));
+        assert!(reused_signature(&signature, &twin, true));
+        assert!(!reused_signature(&signature, &twin, false));
+        assert!(!reused_signature(&signature, &signature, true));
+
+        // a signature which recovers nobody is never accepted
+        let invalid = [word(hash), word(v), word(U256::ZERO), word(s)].concat();
+        assert!(!reused_signature(&invalid, &invalid, false));
+    }
+
+    #[test]
+    fn hijack_code_test() {
+        let receiver = Address::repeat_byte(0xaa);
+        let callback = TxData {
+            balance: U256::from(0x2a),
+            number: U256::ZERO,
+            timestamp: U256::ZERO,
+            coinbase: Address::ZERO,
+            prevrandao: U256::ZERO,
+            basefee: U256::ZERO,
+            blob_basefee: U256::ZERO,
+            blobhashes: vec![],
+            input_data: vec![U256::from(0x11), U256::from(0x22)],
+            storage_upd: vec![],
+            origin: None,
+        };
+        let code = hijack_code(receiver, &callback, true);
+
+        // the guard jumps onto the final stop and the calldata is copied from the code's tail
+        assert_eq!(&code[0x57..0x59], &[0x5b, 0x00]);
+        assert_eq!(&code[0x10..0x12], &[0x00, 0x59]);
+        assert_eq!(&code[0x59..], &convert_data_to_bytes(callback.input_data.clone())[..]);
+        assert_eq!(U256::from_be_slice(&code[0x1f..0x3f]), callback.balance);
+        assert_eq!(&code[0x40..0x54], receiver.as_slice());
+
+        // without the guard the relay calls on every invocation
+        let code = hijack_code(receiver, &callback, false);
+        assert_eq!(&code[0x04..0x06], &[0x00, 0x4d]);
+        assert_eq!(&code[0x4b..0x4d], &[0x5b, 0x00]);
+        assert_eq!(&code[0x4d..], &convert_data_to_bytes(callback.input_data.clone())[..]);
+    }
 }
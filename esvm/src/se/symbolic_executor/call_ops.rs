@@ -1,26 +1,328 @@
 use std::sync::Arc;
 
 use crate::se::{
-    config::HIJACK_ADDR,
-    env::{AccountId, TxId},
+    config::{ADDRESS_MASK, EIP1967_SLOTS, HIJACK_ADDR, MAX_CODE_SIZE, MAX_INITCODE_SIZE},
+    env::{AccountId, TxId, fresh_var_name},
     expr::{
         bval::*,
-        symbolic_memory::{memcopy, memset},
+        symbolic_memory::{
+            MVal, MemoryType, SymbolicMemory, byte_write, create_new_memory, memcopy, memset,
+            memset_unlimited, word_write,
+        },
     },
     symbolic_analysis::{Analysis, AnalysisMode},
     symbolic_edge::*,
-    symbolic_state::{Flags, HaltingReason, ResultState, SeState},
+    symbolic_state::{
+        BlockDependentEffect, EcRecover, Effect, Flags, HaltingReason, ResultState, SeState,
+        StorageCollision,
+    },
 };
 
+use super::stack_ops::{block_dependent, masked_values};
+
 pub fn create_account(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
-    if let Some(_) = res.pop3() {
-        let callres = fresh_var("account_creation");
-        let constraint = or(&eql(&callres, &one()), &eql(&callres, &zero()));
-        res.push_constraint(constraint);
-        res.stack.push(callres);
+    if let Some((value, offset, size)) = res.pop3() {
+        let addr = create_address(&mut res);
+        return deploy_contract(res, &value, &offset, &size, &addr);
     }
-    vec![(res, edge_exec())]
+    vec![]
+}
+
+// keccak256(rlp([sender, nonce]))[12..]
+fn create_address(s: &mut SeState) -> BVal {
+    let nonce = s.account().nonce;
+    let nonce_rlp = match nonce {
+        0 => vec![0x80],
+        1..=0x7f => vec![nonce as u8],
+        _ => {
+            let bytes = nonce.to_be_bytes();
+            let start = bytes.iter().position(|b| *b != 0).unwrap();
+            let mut rlp = vec![0x80 + (bytes.len() - start) as u8];
+            rlp.extend_from_slice(&bytes[start..]);
+            rlp
+        }
+    };
+    // 0x94 prefix for the 20 address bytes followed by the encoded nonce
+    let payload_len = 21 + nonce_rlp.len();
+
+    let addr = Arc::clone(&s.account().addr);
+    let name = fresh_var_name(&format!("{}_create", s.account().name));
+    let preimage;
+    {
+        let memory = Arc::make_mut(&mut s.memory);
+        let mut mem = create_new_memory(memory, name, MemoryType::Memory, None, None);
+        // the list prefix is located at offset 10, followed by the 20 address bytes
+        mem = word_write(memory, mem, &zero(), &addr);
+        mem = byte_write(
+            memory,
+            mem,
+            &const_usize(10),
+            &const_usize(0xc0 + payload_len),
+        );
+        mem = byte_write(memory, mem, &const_usize(11), &const_usize(0x94));
+        for (i, byte) in nonce_rlp.iter().enumerate() {
+            mem = byte_write(
+                memory,
+                mem,
+                &const_usize(32 + i),
+                &const_usize(*byte as usize),
+            );
+        }
+        preimage = mem;
+    }
+
+    let hash = sha3(
+        &s.memory,
+        preimage,
+        &const_usize(10),
+        &const_usize(1 + payload_len),
+    );
+    if !FVal::is_constant(&hash) {
+        s.record_keccak_result(&hash);
+    }
+    and(&hash, &const256(ADDRESS_MASK))
+}
+
+pub fn create2_account(s: &SeState) -> Vec<(SeState, EdgeType)> {
+    let mut res = s.create_succ();
+    if let Some((value, offset, size, salt)) = res.pop4() {
+        let addr = create2_address(&mut res, &offset, &size, &salt);
+        return deploy_contract(res, &value, &offset, &size, &addr);
+    }
+    vec![]
+}
+
+// keccak256(0xff ++ sender ++ salt ++ keccak256(init_code))[12..]
+fn create2_address(s: &mut SeState, offset: &BVal, size: &BVal, salt: &BVal) -> BVal {
+    let code_hash = sha3(&s.memory, s.mem, offset, size);
+    if !FVal::is_constant(&code_hash) {
+        s.record_keccak_result(&code_hash);
+    }
+
+    let prefix = or(
+        &shl(&const_usize(0xff), &const_usize(160)),
+        &s.account().addr,
+    );
+    let name = fresh_var_name(&format!("{}_create2", s.account().name));
+    let preimage;
+    {
+        let memory = Arc::make_mut(&mut s.memory);
+        let mut mem = create_new_memory(memory, name, MemoryType::Memory, None, None);
+        // the 0xff byte is located at offset 11, followed by the 20 address bytes
+        mem = word_write(memory, mem, &zero(), &prefix);
+        mem = word_write(memory, mem, &const_usize(32), salt);
+        mem = word_write(memory, mem, &const_usize(64), &code_hash);
+        preimage = mem;
+    }
+
+    let hash = sha3(&s.memory, preimage, &const_usize(11), &const_usize(85));
+    if !FVal::is_constant(&hash) {
+        s.record_keccak_result(&hash);
+    }
+    and(&hash, &const256(ADDRESS_MASK))
+}
+
+// load the memory region concretely, fails if a single byte is symbolic
+fn concrete_bytes(
+    memory: &SymbolicMemory,
+    mem: MVal,
+    offset: &BVal,
+    size: usize,
+) -> Option<Vec<u8>> {
+    (0..size)
+        .map(|i| {
+            let byte = mload8(memory, mem, &add(offset, &const_usize(i)));
+            FVal::as_usize(&byte).map(|b| b as u8)
+        })
+        .collect()
+}
+
+fn deploy_contract(
+    mut s: SeState,
+    value: &BVal,
+    offset: &BVal,
+    size: &BVal,
+    addr: &BVal,
+) -> Vec<(SeState, EdgeType)> {
+    if s.flags.contains(Flags::STATIC) {
+        warn!("State changing create operation during static call, dropping path!");
+        return vec![];
+    }
+    if s.call_depth > s.config().call_depth_limit {
+        info!("Call depth limit reached, dropping path!");
+        return vec![];
+    }
+
+    // the nonce of the sender is increased regardless of the outcome of the init code
+    {
+        let account = s.account;
+        Arc::make_mut(&mut s.env).get_account_mut(&account).nonce += 1;
+    }
+
+    // the creation can always fail, e.g. due to insufficient balance or gas
+    let mut transitions = vec![create_deploy_failure_state(&s)];
+
+    let initcode = match FVal::as_usize(size) {
+        Some(size) if size <= MAX_INITCODE_SIZE => concrete_bytes(&s.memory, s.mem, offset, size),
+        _ => None,
+    };
+    let initcode = match initcode {
+        Some(code) => code,
+        None => {
+            warn!("Could not load init code concretely, only considering failed creation!");
+            return transitions;
+        }
+    };
+    if s.env.try_get_account_id_by_addr(addr).is_some() {
+        info!(
+            "Contract creation collides with existing account {:?}",
+            addr
+        );
+        return transitions;
+    }
+    if !cfg!(feature = "calls") {
+        return transitions;
+    }
+
+    let mut create = s.fork();
+    let id;
+    {
+        let env = Arc::make_mut(&mut create.env);
+        id = env.new_account(
+            Arc::make_mut(&mut create.memory),
+            "created",
+            addr,
+            Some(initcode.clone()),
+            &zero(),
+        );
+        let memory = Arc::make_mut(&mut create.memory);
+        let acc = env.get_account_mut(&id);
+        acc.created = true;
+        // a freshly created account starts with empty storage, even in symbolic storage mode
+        acc.storage = memset_unlimited(memory, acc.storage, &zero(), Some(zero()));
+    }
+    info!(
+        "Executing init code from {:?} for new account {:?}",
+        create.account().addr,
+        addr
+    );
+
+    let gas = Arc::clone(&create.input_tx().gas);
+    let tx = create_new_outgoing(
+        &mut create,
+        &gas,
+        &zero(),
+        &zero(),
+        &zero(),
+        value,
+        TxType::Call(id),
+    );
+    if !create.check_sat() {
+        return transitions;
+    }
+
+    for end_state in execute_contract(&create, &create.account, &id, &tx, &initcode) {
+        if let Some(transition) = create_deploy_return_state(&s, end_state, id, addr) {
+            transitions.push(transition);
+        }
+    }
+    transitions
+}
+
+fn create_deploy_failure_state(s: &SeState) -> (SeState, EdgeType) {
+    let mut failure = s.fork();
+    failure.reset_returndata();
+    failure.push(zero());
+    failure.flags |= Flags::FAILURE;
+    (failure, edge_call_ret())
+}
+
+fn create_deploy_return_state(
+    s: &SeState,
+    end_state: ResultState,
+    id: AccountId,
+    addr: &BVal,
+) -> Option<(SeState, EdgeType)> {
+    let mut return_state = s.fork();
+
+    // constraints
+    return_state.set_constraints(&end_state.constraints);
+
+    // update old memory
+    Arc::make_mut(&mut return_state.old_memory).insert(end_state.mem);
+
+    // reads
+    return_state.reads = Arc::clone(&end_state.reads);
+
+    // flags
+    return_state.flags = (end_state.flags & Flags::NON_STATIC_MASK) | s.flags;
+
+    // keccak
+    return_state.keccaks = Arc::clone(&end_state.keccaks);
+
+    // memory
+    return_state.memory = Arc::clone(&end_state.memory);
+
+    // tracker
+    return_state.constraints_tracker = Arc::clone(&end_state.constraints_tracker);
+
+    // logs, failed calls already dropped their own on revert
+    return_state.logs = Arc::clone(&end_state.logs);
+
+    match end_state.halting_reason {
+        Some(HaltingReason::Return) => {
+            let size = FVal::as_usize(&end_state.returndata_size);
+            match (end_state.returndata, size) {
+                // code size limit, see EIP-170
+                (Some(_), Some(size)) if size > MAX_CODE_SIZE => {
+                    return_state.reset_returndata();
+                    return_state.push(zero());
+                }
+                (Some(data), Some(size)) => {
+                    // the returned data of the init code is the runtime code of the new account
+                    let code = match concrete_bytes(&end_state.memory, data, &zero(), size) {
+                        Some(code) => code,
+                        None => {
+                            warn!("Could not load runtime code concretely, dropping path!");
+                            return None;
+                        }
+                    };
+                    deploy_code(&mut return_state, &end_state, id, addr, Some(code));
+                }
+                _ => {
+                    warn!("Could not load runtime code concretely, dropping path!");
+                    return None;
+                }
+            }
+        }
+        Some(HaltingReason::Stop) | Some(HaltingReason::Selfdestruct) => {
+            deploy_code(&mut return_state, &end_state, id, addr, None);
+        }
+        Some(HaltingReason::Revert) | Some(HaltingReason::Invalid) => {
+            // all state changes of the creation are reverted, keep the env of the caller
+            return_state.returndata = end_state.returndata;
+            return_state.returndata_size = end_state.returndata_size;
+            return_state.push(zero());
+        }
+        None => panic!("Did not set halting state correctly / did not correctly return end states"),
+    }
+
+    Some((return_state, edge_call_ret()))
+}
+
+fn deploy_code(
+    return_state: &mut SeState,
+    end_state: &ResultState,
+    id: AccountId,
+    addr: &BVal,
+    code: Option<Vec<u8>>,
+) {
+    let mut env = Arc::clone(&end_state.env);
+    Arc::make_mut(&mut env).set_code(&id, code.filter(|c| !c.is_empty()));
+    return_state.env = env;
+    return_state.reset_returndata();
+    return_state.push(Arc::clone(addr));
 }
 
 fn call_op<F>(to: &BVal, s: SeState, include_self: bool, f: F) -> Vec<(SeState, EdgeType)>
@@ -75,6 +377,39 @@ pub fn extcode_size(s: &SeState) -> Vec<(SeState, EdgeType)> {
     vec![]
 }
 
+// fork over all known accounts in the execution env, unknown accounts do not exist and hash to 0
+pub fn extcode_hash(s: &SeState) -> Vec<(SeState, EdgeType)> {
+    let mut res = s.create_succ();
+    if let Some(to) = res.pop1() {
+        let mut missing = res.fork();
+        for (addr, _) in res.env.get_addresses() {
+            missing.push_constraint(neql(&to, &addr));
+        }
+
+        let mut states = call_op(&to, res, false, |to, addr, id, mut check| {
+            // add constraint along this path
+            check.push_constraint(eql(to, addr));
+            // accounts without code hash to keccak256("")
+            let hash = match check.env.get_account(&id).code() {
+                Some(code) => const_vec(&tiny_keccak::keccak256(code)),
+                None => const_vec(&tiny_keccak::keccak256(&[])),
+            };
+            if !check.check_sat() {
+                return None;
+            }
+            check.push(hash);
+            Some(vec![(check, edge_exec())])
+        });
+
+        if missing.check_sat() {
+            missing.push(zero());
+            states.push((missing, edge_exec()));
+        }
+        return states;
+    }
+    vec![]
+}
+
 // fork over all known accounts in the execution env
 pub fn balance(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
@@ -96,6 +431,8 @@ pub fn balance(s: &SeState) -> Vec<(SeState, EdgeType)> {
 
 pub fn selfdestruct(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
+    res.record_privileged_operation(s.pc);
+    res.record_effect(Effect::Selfdestruct);
     if let Some(to) = res.pop1() {
         return call_op(&to, res, true, |to, addr, id, mut fork| {
             fork.push_constraint(eql(to, addr));
@@ -169,15 +506,37 @@ pub fn new_call(s: &SeState, call_type: CallType) -> Vec<(SeState, EdgeType)> {
             res.push_constraint(eql(value, &zero()));
         }
 
+        if call_type.is_ecrecover(to) {
+            res.flags |= Flags::ECDSA_RECOVERY;
+            return vec![(recover_signer(res, &args), edge_call_ret())];
+        }
+
         let mut transitions = vec![];
 
+        if call_type == CallType::Call {
+            res.record_privileged_operation(s.pc);
+            if !std::matches!(FVal::check_truth(value), SymbolicTruth::False) {
+                res.record_effect(Effect::ValueTransfer);
+                if s.flags.contains(Flags::BLOCK_DEPENDENCE)
+                    || block_dependent(s, to)
+                    || block_dependent(s, value)
+                {
+                    res.record_block_dependence(BlockDependentEffect::Transfer {
+                        pc: s.pc,
+                        value: Arc::clone(value),
+                    });
+                }
+            }
+        }
+        if call_type == CallType::DelegateCall {
+            res.record_effect(Effect::DelegateCall);
+        }
+
         // check for reentrancy, control flow hijack and precompiled contracts
         match call_type {
             CallType::Call | CallType::StaticCall => {
                 set_precompiled_contracts_flags(&mut res, to);
-                if let Some(reentrancy) = check_for_reentrancy(&res, &args) {
-                    transitions.push(reentrancy);
-                }
+                transitions.append(&mut check_for_reentrancy(&res, &args));
             }
             CallType::CallCode | CallType::DelegateCall => {
                 if let Some(hijack) = check_for_control_flow_hijack(&res, to) {
@@ -200,7 +559,10 @@ pub fn new_call(s: &SeState, call_type: CallType) -> Vec<(SeState, EdgeType)> {
 
             // Create a failure state to simulate the call failing
             // create and push a failure state
-            trans.push(create_failure_state(&call, id));
+            let (mut failure, edge) = create_failure_state(&call, id);
+            let result = Arc::clone(failure.stack.last().unwrap());
+            failure.record_failed_call(s.pc, id, &result);
+            trans.push((failure, edge));
 
             let tx_type = match call_type {
                 CallType::Call | CallType::StaticCall => TxType::Call(id),
@@ -268,6 +630,10 @@ pub fn new_call(s: &SeState, call_type: CallType) -> Vec<(SeState, EdgeType)> {
                     }
                 };
 
+                let pointers = match call_type {
+                    CallType::DelegateCall => pointer_slots(&call, to),
+                    _ => vec![],
+                };
                 for end_state in end_states {
                     let callres_execution = fresh_var(&format!(
                         "{}_{}_{}_execution",
@@ -276,13 +642,15 @@ pub fn new_call(s: &SeState, call_type: CallType) -> Vec<(SeState, EdgeType)> {
                         call.env.get_account(&id).name
                     ));
 
-                    trans.push(create_return_state(
-                        &call,
-                        end_state,
-                        callres_execution,
-                        out_off,
-                        out_size,
-                    ));
+                    // writes behind a check of the caller against the proxy's storage are
+                    // intended, e.g. an UUPS upgradeTo restricted to the owner
+                    let guarded = !end_state.caller_checks.is_empty();
+                    let (mut ret, edge) =
+                        create_return_state(&call, end_state, callres_execution, out_off, out_size);
+                    if !guarded {
+                        check_for_storage_collisions(&call, &mut ret, &pointers);
+                    }
+                    trans.push((ret, edge));
                 }
             }
             Some(trans)
@@ -292,7 +660,104 @@ pub fn new_call(s: &SeState, call_type: CallType) -> Vec<(SeState, EdgeType)> {
     vec![]
 }
 
+// The precompile is modelled with a fresh signer, kept consistent across all recoveries of the
+// same signature on the path. Invalid v, r or s values recover the zero address.
+fn recover_signer(mut s: SeState, args: &CallArgs) -> SeState {
+    let word = |i: usize| add(&args.in_off, &const_usize(i * 32));
+    let recovery = EcRecover {
+        hash: mload(&s.memory, s.mem, &word(0)),
+        v: mload(&s.memory, s.mem, &word(1)),
+        r: mload(&s.memory, s.mem, &word(2)),
+        s: mload(&s.memory, s.mem, &word(3)),
+        signer: and(
+            &fresh_var(&format!("ecrecover_{}_signer", s.account().name)),
+            &const256(ADDRESS_MASK),
+        ),
+    };
+    let invalid = or(
+        &and(
+            &neql(&recovery.v, &const_usize(27)),
+            &neql(&recovery.v, &const_usize(28)),
+        ),
+        &or(&eql(&recovery.r, &zero()), &eql(&recovery.s, &zero())),
+    );
+    s.push_constraint(implies(&invalid, &eql(&recovery.signer, &zero())));
+
+    let memory = Arc::make_mut(&mut s.memory);
+    let returndata = create_new_memory(
+        memory,
+        fresh_var_name("ecrecover_returndata"),
+        MemoryType::Data,
+        Some(const_usize(32)),
+        None,
+    );
+    let returndata = word_write(memory, returndata, &zero(), &recovery.signer);
+    s.mem = memcopy(
+        memory,
+        s.mem,
+        returndata,
+        &args.out_off,
+        &const_usize(0),
+        &args.out_size,
+    );
+    s.returndata = Some(returndata);
+    s.returndata_size = const_usize(32);
+    s.record_ecrecover(recovery);
+
+    let callres = fresh_var(&format!("ecrecover_{}_res", s.account().name));
+    s.push_constraint(eql(&callres, &one()));
+    s.stack.push(callres);
+    s
+}
+
+/// Storage slots the caller reads as implementation or admin pointer, i.e. the slot the target
+/// is loaded from, the owner and access control slots, and the EIP-1967 slots
+fn pointer_slots(s: &SeState, to: &BVal) -> Vec<BVal> {
+    let mut slots: Vec<BVal> = EIP1967_SLOTS.iter().map(|slot| const256(slot)).collect();
+    let loaded = masked_values(to)
+        .into_iter()
+        .filter_map(|val| match val.val() {
+            Val256::FSLoad(_, slot) if FVal::is_constant(slot) => Some(slot),
+            _ => None,
+        });
+    let guarding = s
+        .privileged_slots
+        .iter()
+        .filter(|(id, _)| *id == s.account)
+        .map(|(_, slot)| slot);
+    for slot in loaded.chain(guarding).chain(s.account().owner.iter()) {
+        if !slots.contains(slot) {
+            slots.push(Arc::clone(slot));
+        }
+    }
+    slots
+}
+
+// The delegated code runs on the caller's storage, any write to a pointer slot of the caller is
+// recorded together with the condition under which it changes the slot.
+fn check_for_storage_collisions(s: &SeState, ret: &mut SeState, pointers: &[BVal]) {
+    let (before, after) = (s.account().storage, ret.account().storage);
+    if before == after {
+        return;
+    }
+    for slot in pointers {
+        let overwritten = neql(
+            &sload(&ret.memory, before, slot),
+            &sload(&ret.memory, after, slot),
+        );
+        ret.record_storage_collision(StorageCollision {
+            pc: s.pc,
+            slot: Arc::clone(slot),
+            overwritten,
+        });
+    }
+}
+
 impl CallType {
+    fn is_ecrecover(self, to: &BVal) -> bool {
+        std::matches!(self, CallType::Call | CallType::StaticCall) && FVal::as_usize(to) == Some(0x1)
+    }
+
     fn is_static_call(self) -> bool {
         if let CallType::StaticCall = self {
             true
@@ -455,6 +920,13 @@ fn create_return_state(
     // tracker
     return_state.constraints_tracker = Arc::clone(&end_state.constraints_tracker);
 
+    // logs, failed calls already dropped their own on revert
+    return_state.logs = Arc::clone(&end_state.logs);
+
+    // signatures recovered in the callee
+    return_state.ecrecovers = Arc::clone(&end_state.ecrecovers);
+    return_state.origin_guarded_ops = Arc::clone(&end_state.origin_guarded_ops);
+
     // clone return data if available and set callres constraint
     match end_state.halting_reason {
         Some(HaltingReason::Revert | HaltingReason::Invalid) => {
@@ -462,6 +934,7 @@ fn create_return_state(
             return_state.returndata = end_state.returndata;
             return_state.returndata_size = end_state.returndata_size;
             return_state.failed_assert = end_state.failed_assert;
+            return_state.panic_code = end_state.panic_code;
             return_state.mem = memcopy(
                 Arc::make_mut(&mut memory),
                 s.mem,
@@ -572,9 +1045,14 @@ fn check_for_control_flow_hijack(s: &SeState, to: &BVal) -> Option<(SeState, Edg
     None
 }
 
-fn check_for_reentrancy(s: &SeState, args: &CallArgs) -> Option<(SeState, EdgeType)> {
+// A call to the hijack account with enough gas lets the attacker re-enter the current account
+// with an arbitrary transaction before the call returns. We only re-enter once per path.
+fn check_for_reentrancy(s: &SeState, args: &CallArgs) -> Vec<(SeState, EdgeType)> {
+    if s.flags.contains(Flags::REENTRANCY) {
+        return vec![];
+    }
     let mut reentrancy = s.fork();
-    let reentrancy_id = s
+    let reentrancy_id = *s
         .env
         .try_get_account_id_by_addr(&const256(HIJACK_ADDR))
         .unwrap();
@@ -587,23 +1065,94 @@ fn check_for_reentrancy(s: &SeState, args: &CallArgs) -> Option<(SeState, EdgeTy
         &args.out_size,
         &args.in_off,
         &args.value,
-        TxType::Call(*reentrancy_id),
+        TxType::Call(reentrancy_id),
     );
 
     reentrancy.push_constraint(eql(&args.to, &const256(HIJACK_ADDR)));
     reentrancy.push_constraint(lt(&const_usize(2300), &args.gas));
 
-    if reentrancy.check_sat() {
-        reentrancy.flags |= Flags::REENTRANCY;
-
-        let callres_reeentrancy =
-            fresh_var(&format!("reentrancy_{}_res", reentrancy.account().name,));
-        reentrancy.push_constraint(eql(&callres_reeentrancy, &one()));
-        reentrancy.stack.push(callres_reeentrancy);
+    if !reentrancy.check_sat() {
+        return vec![];
+    }
+    reentrancy.flags |= Flags::REENTRANCY;
+
+    let victim = reentrancy.account;
+    let balance = Arc::clone(&reentrancy.account().balance);
+    let origin = Arc::clone(&reentrancy.input_tx().origin);
+    let tx = Arc::make_mut(&mut reentrancy.env).new_reentrancy_tx(
+        Arc::make_mut(&mut reentrancy.memory),
+        reentrancy_id,
+        victim,
+        &origin,
+    );
+    let code = match reentrancy.account().code() {
+        Some(code) => code.clone(),
+        None => return vec![],
+    };
+    info!(
+        "Re-entering {:?} from the hijack account",
+        reentrancy.account().addr
+    );
 
-        return Some((reentrancy, edge_call_ret()));
+    let mut transitions = vec![];
+    for end_state in execute_contract(&reentrancy, &reentrancy_id, &victim, &tx, &code) {
+        // a failing re-entrant call could simply be ignored by the attacker
+        if let Some(HaltingReason::Revert | HaltingReason::Invalid) = end_state.halting_reason {
+            continue;
+        }
+        transitions.push(create_reentrancy_return_state(&reentrancy, end_state, &balance));
     }
-    None
+    transitions
+}
+
+fn create_reentrancy_return_state(
+    s: &SeState,
+    end_state: ResultState,
+    balance: &BVal,
+) -> (SeState, EdgeType) {
+    let mut return_state = s.fork();
+
+    // copy env
+    return_state.env = Arc::clone(&end_state.env);
+    let victim = return_state.account;
+    let returned_balance = Arc::clone(&return_state.env.get_account(&victim).balance);
+    Arc::make_mut(&mut return_state.env).record_reentrancy_balances(balance, &returned_balance);
+
+    // constraints
+    return_state.set_constraints(&end_state.constraints);
+
+    // update old memory
+    Arc::make_mut(&mut return_state.old_memory).insert(end_state.mem);
+
+    // reads
+    return_state.reads = Arc::clone(&end_state.reads);
+
+    // flags
+    return_state.flags = (end_state.flags & Flags::NON_STATIC_MASK) | s.flags;
+
+    // keccak
+    return_state.keccaks = Arc::clone(&end_state.keccaks);
+
+    // memory
+    return_state.memory = Arc::clone(&end_state.memory);
+
+    // tracker
+    return_state.constraints_tracker = Arc::clone(&end_state.constraints_tracker);
+
+    // logs
+    return_state.logs = Arc::clone(&end_state.logs);
+
+    // signatures recovered in the re-entrant call
+    return_state.ecrecovers = Arc::clone(&end_state.ecrecovers);
+    return_state.origin_guarded_ops = Arc::clone(&end_state.origin_guarded_ops);
+
+    // the hijack contract returns without data
+    return_state.reset_returndata();
+    let callres_reentrancy = fresh_var(&format!("reentrancy_{}_res", return_state.account().name));
+    return_state.push_constraint(eql(&callres_reentrancy, &one()));
+    return_state.stack.push(callres_reentrancy);
+
+    (return_state, edge_call_ret())
 }
 
 #[cfg(test)]
@@ -617,7 +1166,10 @@ mod tests {
 
     use crate::se::config::{ORIGIN, TARGET_ADDR};
     use crate::se::symbolic_analysis::CONFIG;
-    use crate::test_helpers::{generate_test_graph, generate_test_state};
+    use crate::se::symbolic_state::FailedCall;
+    use crate::test_helpers::{
+        generate_test_graph, generate_test_state, generate_test_state_with_code,
+    };
 
     //  This "unit" test is terribly designed, it tests way to much...
     #[test]
@@ -689,6 +1241,205 @@ mod tests {
         assert_eq!(attacker_balance, attacker.balance);
     }
 
+    #[test]
+    fn reentrancy_test() {
+        // sstore(0, 1); stop
+        let code = vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00];
+        let mut state = generate_test_state_with_code(&code);
+        state.push(const_usize(0)); // out_size
+        state.push(const_usize(0)); // out_off
+        state.push(const_usize(0)); // in_size
+        state.push(const_usize(0)); // in_off
+        state.push(const_usize(0)); // value
+        state.push(const256(HIJACK_ADDR)); // to
+        state.push(const_usize(10_000)); // gas
+
+        let transitions = new_call(&state, CallType::Call);
+        let reentered: Vec<_> = transitions
+            .into_iter()
+            .map(|(s, _)| s)
+            .filter(|s| s.flags.contains(Flags::REENTRANCY))
+            .collect();
+        assert_eq!(1, reentered.len());
+
+        let res = &reentered[0];
+        assert_eq!(1, res.env.reentrancy_txs().len());
+        assert!(res.env.reentrancy_balances().is_some());
+        assert_ne!(state.account().storage, res.account().storage);
+
+        // a stipend is not enough to re-enter
+        state.stack.pop();
+        state.push(const_usize(2300));
+        assert!(
+            new_call(&state, CallType::Call)
+                .iter()
+                .all(|(s, _)| !s.flags.contains(Flags::REENTRANCY))
+        );
+    }
+
+    #[test]
+    fn failed_call_test() {
+        let mut state = generate_test_state();
+        state.push(const_usize(0)); // out_size
+        state.push(const_usize(0)); // out_off
+        state.push(const_usize(0)); // in_size
+        state.push(const_usize(0)); // in_off
+        state.push(const_usize(1)); // value
+        state.push(const256(HIJACK_ADDR)); // to
+        state.push(const_usize(2300)); // gas
+        let hijack = *state
+            .env
+            .try_get_account_id_by_addr(&const256(HIJACK_ADDR))
+            .unwrap();
+
+        let transitions = new_call(&state, CallType::Call);
+        assert!(
+            transitions
+                .iter()
+                .any(|(res, _)| res.flags.contains(Flags::FAILURE))
+        );
+        for (res, _) in transitions.iter() {
+            if res.flags.contains(Flags::FAILURE) {
+                assert_eq!(
+                    vec![FailedCall {
+                        pc: state.pc,
+                        callee: hijack,
+                        result: Arc::clone(res.stack.last().unwrap()),
+                    }],
+                    *res.failed_calls
+                );
+            } else {
+                assert!(res.failed_calls.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn ecrecover_test() {
+        let mut state = generate_test_state();
+        // hash = 0, v = 27, r = 1, s = 1
+        for (addr, val) in [(0x20, 27), (0x40, 1), (0x60, 1)] {
+            let memory = Arc::make_mut(&mut state.memory);
+            state.mem = word_write(memory, state.mem, &const_usize(addr), &const_usize(val));
+        }
+        for _ in 0..2 {
+            state.push(const_usize(0x20)); // out_size
+            state.push(const_usize(0x80)); // out_off
+            state.push(const_usize(0x80)); // in_size
+            state.push(const_usize(0x0)); // in_off
+            state.push(const_usize(0x1)); // to
+            state.push(const_usize(3000)); // gas
+            let mut transitions = new_call(&state, CallType::StaticCall);
+            assert_eq!(1, transitions.len());
+            state = transitions.pop().unwrap().0;
+            assert!(state.flags.contains(Flags::ECDSA_RECOVERY));
+        }
+        assert_eq!(2, state.ecrecovers.len());
+
+        let first = &state.ecrecovers[0];
+        let second = &state.ecrecovers[1];
+        let mut check = state.clone();
+        check.push_constraint(neql(&first.signer, &zero()));
+        assert!(check.check_sat());
+        check.push_constraint(neql(&first.signer, &second.signer));
+        assert!(!check.check_sat());
+    }
+
+    #[test]
+    fn storage_collision_test() {
+        // sstore(0, 1); stop
+        let code = vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00];
+        let mut state = generate_test_state_with_code(&code);
+        let victim = state.account;
+        Arc::make_mut(&mut state.privileged_slots).insert((victim, const_usize(0)));
+        let to = Arc::clone(&state.account().addr);
+        state.push(const_usize(0)); // out_size
+        state.push(const_usize(0)); // out_off
+        state.push(const_usize(0)); // in_size
+        state.push(const_usize(0)); // in_off
+        state.push(to); // to
+        state.push(const_usize(10_000)); // gas
+
+        let transitions = new_call(&state, CallType::DelegateCall);
+        let returned: Vec<_> = transitions
+            .into_iter()
+            .map(|(s, _)| s)
+            .filter(|s| !s.flags.contains(Flags::FAILURE))
+            .collect();
+        assert_eq!(1, returned.len());
+
+        let res = &returned[0];
+        for collision in res.storage_collisions.iter() {
+            let mut check = res.clone();
+            check.push_constraint(Arc::clone(&collision.overwritten));
+            assert_eq!(collision.slot == const_usize(0), check.check_sat());
+        }
+        assert!(
+            res.storage_collisions
+                .iter()
+                .any(|c| c.slot == const_usize(0))
+        );
+    }
+
+    #[test]
+    fn guarded_storage_collision_test() {
+        // if (caller == sload(0)) sstore(0, 1); stop
+        let code = vec![
+            0x33, 0x60, 0x00, 0x54, 0x14, 0x60, 0x09, 0x57, 0x00, 0x5b, 0x60, 0x01, 0x60, 0x00,
+            0x55, 0x00,
+        ];
+        let mut state = generate_test_state_with_code(&code);
+        let victim = state.account;
+        Arc::make_mut(&mut state.privileged_slots).insert((victim, const_usize(0)));
+        let to = Arc::clone(&state.account().addr);
+        state.push(const_usize(0)); // out_size
+        state.push(const_usize(0)); // out_off
+        state.push(const_usize(0)); // in_size
+        state.push(const_usize(0)); // in_off
+        state.push(to); // to
+        state.push(const_usize(10_000)); // gas
+
+        let transitions = new_call(&state, CallType::DelegateCall);
+        let returned: Vec<_> = transitions
+            .into_iter()
+            .map(|(s, _)| s)
+            .filter(|s| !s.flags.contains(Flags::FAILURE))
+            .collect();
+        assert_eq!(2, returned.len());
+        assert!(returned
+            .iter()
+            .any(|s| s.account().storage != state.account().storage));
+        assert!(returned.iter().all(|s| s.storage_collisions.is_empty()));
+    }
+
+    #[test]
+    fn extcode_hash_test() {
+        let ins = vec![];
+        let g = generate_test_graph(ins);
+
+        let mut state = g.get_state_by_id(1).clone();
+        let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        Arc::make_mut(&mut state.env).new_account(
+            Arc::make_mut(&mut state.memory),
+            "hash_acc",
+            &const_usize(0xAA),
+            Some(code.clone()),
+            &const_usize(0),
+        );
+
+        state.push(const_usize(0xAA));
+        let mut transitions = extcode_hash(&state);
+        assert_eq!(1, transitions.len());
+        let res = transitions.pop().unwrap().0;
+        assert_eq!(const_vec(&tiny_keccak::keccak256(&code)), res.stack[0]);
+
+        state.push(const_usize(0xBB));
+        let mut transitions = extcode_hash(&state);
+        assert_eq!(1, transitions.len());
+        let res = transitions.pop().unwrap().0;
+        assert_eq!(zero(), res.stack[1]);
+    }
+
     #[test]
     fn create_new_outgoing_test() {
         let ins = vec![];
@@ -718,6 +1469,97 @@ mod tests {
         let _ = state.env.get_tx(&id);
     }
 
+    #[test]
+    fn create_address_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let mut state = generate_test_state();
+        // PUSH1 0x00 PUSH1 0x00 RETURN
+        let initcode = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        for (i, byte) in initcode.iter().enumerate() {
+            state.mem = byte_write(
+                Arc::make_mut(&mut state.memory),
+                state.mem,
+                &const_usize(i),
+                &const_usize(*byte as usize),
+            );
+        }
+        state.push(const_usize(initcode.len())); // size
+        state.push(const_usize(0)); // offset
+        state.push(const_usize(0)); // value
+        assert_eq!(1, state.account().nonce);
+
+        // rlp([sender, 1])
+        let mut preimage = vec![0xd6, 0x94];
+        let mut sender = [0u8; 32];
+        U256::from_dec_str(TARGET_ADDR)
+            .unwrap()
+            .to_big_endian(&mut sender);
+        preimage.extend_from_slice(&sender[12..]);
+        preimage.push(0x01);
+        let correct_addr = const_vec(&tiny_keccak::keccak256(&preimage)[12..]);
+
+        let mut transitions = create_account(&state);
+        assert_eq!(2, transitions.len());
+
+        let success = transitions.pop().unwrap().0;
+        let fail = transitions.pop().unwrap().0;
+        assert_eq!(zero(), fail.stack[0]);
+        assert_eq!(correct_addr, success.stack[0]);
+        assert_eq!(2, fail.account().nonce);
+        assert_eq!(2, success.account().nonce);
+
+        // empty runtime code
+        let created = success.env.try_get_account_by_addr(&correct_addr).unwrap();
+        assert_eq!(None, created.code());
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
+    #[test]
+    fn create2_address_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let mut state = generate_test_state();
+        // PUSH1 0x01 PUSH1 0x00 MSTORE8 PUSH1 0x01 PUSH1 0x00 RETURN
+        let initcode = vec![0x60, 0x01, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3];
+        for (i, byte) in initcode.iter().enumerate() {
+            state.mem = byte_write(
+                Arc::make_mut(&mut state.memory),
+                state.mem,
+                &const_usize(i),
+                &const_usize(*byte as usize),
+            );
+        }
+        state.push(const_usize(0x42)); // salt
+        state.push(const_usize(initcode.len())); // size
+        state.push(const_usize(0)); // offset
+        state.push(const_usize(0)); // value
+
+        let mut preimage = vec![0xff];
+        let mut sender = [0u8; 32];
+        U256::from_dec_str(TARGET_ADDR)
+            .unwrap()
+            .to_big_endian(&mut sender);
+        preimage.extend_from_slice(&sender[12..]);
+        let mut salt = [0u8; 32];
+        U256::from(0x42u64).to_big_endian(&mut salt);
+        preimage.extend_from_slice(&salt);
+        preimage.extend_from_slice(&tiny_keccak::keccak256(&initcode));
+        let correct_addr = const_vec(&tiny_keccak::keccak256(&preimage)[12..]);
+
+        let mut transitions = create2_account(&state);
+        assert_eq!(2, transitions.len());
+
+        let success = transitions.pop().unwrap().0;
+        let fail = transitions.pop().unwrap().0;
+        assert_eq!(zero(), fail.stack[0]);
+        assert_eq!(correct_addr, success.stack[0]);
+
+        let created = success.env.try_get_account_by_addr(&correct_addr).unwrap();
+        assert!(created.created);
+        assert_eq!(Some(&vec![0x01]), created.code());
+        assert!(fail.env.try_get_account_by_addr(&correct_addr).is_none());
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
     #[test]
     fn call_ops_test() {
         let state = generate_test_graph(vec![]).get_state_by_id(1).clone();
@@ -3,14 +3,9 @@ use crate::se::{
     expr::bval::*,
     symbolic_edge::*,
     symbolic_executor::{call_ops::*, memory_ops::*, stack_ops::*},
-    symbolic_state::{Flags, SeState},
+    symbolic_state::SeState,
 };
 
-fn fail_unknown(i: &Instr) -> Vec<(SeState, EdgeType)> {
-    warn!("encountered unknown instruction {:?}, droping path", i);
-    vec![]
-}
-
 pub fn expensive_computation(s: &SeState) -> bool {
     let ins = s.get_instruction();
     if ins.is_none() {
@@ -18,12 +13,15 @@ pub fn expensive_computation(s: &SeState) -> bool {
     }
     match ins.unwrap() {
         Instr::IExtCodeSize
+        | Instr::IExtCodeHash
         | Instr::IBalance
         | Instr::ISelfDestruct
         | Instr::ICall
         | Instr::IStaticCall
         | Instr::ICallCode
-        | Instr::IDelegateCall => true,
+        | Instr::IDelegateCall
+        | Instr::ICreate
+        | Instr::ICreate2 => true,
         _ => false,
     }
 }
@@ -35,7 +33,12 @@ pub fn symbolic_step(s: &SeState) -> Vec<(SeState, EdgeType)> {
         return vec![];
     }
     let ins = ins.unwrap();
-    match ins {
+    let overflow_sink = if s.config().overflow_check {
+        overflow_sink(s, &ins)
+    } else {
+        None
+    };
+    let mut transitions = match ins {
         Instr::IAdd => arith2(s, |a, b| add(a, b)),
         Instr::ISub => arith2(s, |a, b| sub(a, b)),
         Instr::IMul => arith2(s, |a, b| mul(a, b)),
@@ -77,15 +80,36 @@ pub fn symbolic_step(s: &SeState) -> Vec<(SeState, EdgeType)> {
         Instr::ICoinBase => arith0(s, Some(&s.env.latest_block().coinbase)),
         Instr::ITimeStamp => arith0(s, Some(&s.env.latest_block().timestamp)),
         Instr::INumber => arith0(s, Some(&s.env.latest_block().number)),
-        Instr::IDifficulty => arith0(s, Some(&s.env.latest_block().difficulty)),
+        Instr::IPrevRandao => arith0(s, Some(&s.env.latest_block().prevrandao)),
         Instr::IGasLimit => arith0(s, Some(&s.env.latest_block().gas_limit)),
         Instr::IChainId => arith0(s, Some(&s.env.latest_block().chainid)),
+        Instr::ISelfBalance => arith0(s, Some(&s.account().balance)),
+        Instr::IBaseFee => arith0(s, Some(&s.env.latest_block().basefee)),
+        Instr::IBlobHash => {
+            let blobhashes = s
+                .env
+                .latest_block()
+                .carried_blobhashes(s.config().blob_count);
+            arith1(s, |index| {
+                blobhashes
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .fold(zero(), |hash, (i, blobhash)| {
+                        ite(&eql(index, &const_usize(i)), blobhash, &hash)
+                    })
+            })
+        }
+        Instr::IBlobBaseFee => arith0(s, Some(&s.env.latest_block().blob_basefee)),
         Instr::IPop => pop_n(s, 1),
         Instr::IMLoad => memload(s),
         Instr::IMStore => mstore(s),
         Instr::IMStore8 => mstore8(s),
+        Instr::IMCopy => mcopy(s),
         Instr::ISLoad => storage_load(s),
         Instr::ISStore => sstore(s),
+        Instr::ITLoad => transient_load(s),
+        Instr::ITStore => tstore(s),
         Instr::IJump => jump(s),
         Instr::IJumpIf => jump_if(s),
         Instr::IPC => arith0(s, Some(&const_usize(s.pc))),
@@ -95,13 +119,7 @@ pub fn symbolic_step(s: &SeState) -> Vec<(SeState, EdgeType)> {
         Instr::IPush(a) => arith0(s, Some(&const_vec(&a))),
         Instr::IDup(a) => arith0(s, s.stack.get(s.stack.len() - a)),
         Instr::ISwap(a) => swap(s, a),
-        Instr::ILog(n) => {
-            if s.flags.contains(Flags::STATIC) {
-                warn!("State changing log operation during static call, dropping path!");
-                return vec![];
-            }
-            pop_n(s, n + 2)
-        }
+        Instr::ILog(n) => log(s, n),
         Instr::ICall => new_call(s, CallType::Call),
         Instr::IStaticCall => new_call(s, CallType::StaticCall),
         Instr::ICallCode => new_call(s, CallType::CallCode),
@@ -115,9 +133,14 @@ pub fn symbolic_step(s: &SeState) -> Vec<(SeState, EdgeType)> {
         Instr::IRDataSize => returndata_size(s),
         Instr::IRDataCopy => returndata_copy(s),
         Instr::IExtCodeSize => extcode_size(s),
+        Instr::IExtCodeHash => extcode_hash(s),
         Instr::ISext => sign_extend(s),
         Instr::IExtCodeCopy => ext_code_copy(s),
         Instr::ICreate => create_account(s),
-        Instr::ICreate2 => fail_unknown(&ins),
+        Instr::ICreate2 => create2_account(s),
+    };
+    if let Some(val) = overflow_sink {
+        record_overflows(&mut transitions, s.pc, &val);
     }
+    transitions
 }
@@ -7,28 +7,23 @@ use crate::se::{
         expr::bval::*,
         expr::symbolic_memory::{self, *},
         symbolic_edge::*,
-        symbolic_state::{Flags, HaltingReason, SeState},
+        symbolic_state::{
+            BlockDependentEffect, Effect, Flags, HaltingReason, RevertReason, SeState,
+        },
     };
 
+use super::stack_ops::block_dependent;
+
 pub fn code_copy(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some((mem_addr, code_addr, size)) = res.pop3() {
-        let (code_addr_c, size_c) = match (FVal::as_usize(&code_addr), FVal::as_usize(&size)) {
-            (Some(a), Some(b)) => (a, b),
-            _ => {
-                warn!("Can't get constant values for code copy, dropping path");
-                return vec![];
+        let code = s.account().code().unwrap();
+        return match (FVal::as_usize(&code_addr), FVal::as_usize(&size)) {
+            (Some(code_addr_c), Some(size_c)) => {
+                copy_code_to_mem(s, res, &mem_addr, code_addr_c, size_c, code)
             }
+            _ => copy_code_symbolic(res, &mem_addr, &code_addr, &size, code),
         };
-
-        return copy_code_to_mem(
-            s,
-            res,
-            &mem_addr,
-            code_addr_c,
-            size_c,
-            s.account().code().unwrap(),
-        );
     }
     vec![]
 }
@@ -36,14 +31,6 @@ pub fn code_copy(s: &SeState) -> Vec<(SeState, EdgeType)> {
 pub fn ext_code_copy(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some((addr, mem_addr, code_addr, size)) = res.pop4() {
-        let (code_addr_c, size_c) = match (FVal::as_usize(&code_addr), FVal::as_usize(&size)) {
-            (Some(a), Some(b)) => (a, b),
-            _ => {
-                warn!("Can't get constant values for ext code copy, dropping path");
-                return vec![];
-            }
-        };
-
         let code;
         {
             let acc = if let Some(acc) = s.env.try_get_account_by_addr(&addr) {
@@ -52,14 +39,52 @@ pub fn ext_code_copy(s: &SeState) -> Vec<(SeState, EdgeType)> {
                 return vec![];
             };
 
-            code = acc.code().cloned().unwrap(); // this is super inefficient but w/e
+            // accounts without code copy zeros
+            code = acc.code().cloned().unwrap_or_default(); // this is super inefficient but w/e
         }
 
-        return copy_code_to_mem(s, res, &mem_addr, code_addr_c, size_c, &code);
+        return match (FVal::as_usize(&code_addr), FVal::as_usize(&size)) {
+            (Some(code_addr_c), Some(size_c)) => {
+                copy_code_to_mem(s, res, &mem_addr, code_addr_c, size_c, &code)
+            }
+            _ => copy_code_symbolic(res, &mem_addr, &code_addr, &size, &code),
+        };
     }
     vec![]
 }
 
+// copies with a symbolic offset or size are encoded as a memcopy from a constant code memory, the
+// solver decides the length and reads beyond the code size are zero
+fn copy_code_symbolic(
+    mut res: SeState,
+    mem_addr: &BVal,
+    code_addr: &BVal,
+    size: &BVal,
+    code: &[u8],
+) -> Vec<(SeState, EdgeType)> {
+    let name = fresh_var_name(&format!("{}_code", res.account().name));
+    let memory = Arc::make_mut(&mut res.memory);
+    let mut code_mem = symbolic_memory::create_new_memory(
+        memory,
+        name,
+        MemoryType::Data,
+        Some(const_usize(code.len())),
+        None,
+    );
+    for (i, chunk) in code.chunks(32).enumerate() {
+        let mut word = [0u8; 32];
+        word[..chunk.len()].copy_from_slice(chunk);
+        code_mem = word_write(
+            memory,
+            code_mem,
+            &const_usize(i * 32),
+            &const_u256(word[..].into()),
+        );
+    }
+    res.mem = memcopy(memory, res.mem, code_mem, mem_addr, code_addr, size);
+    vec![(res, edge_exec())]
+}
+
 fn copy_code_to_mem(
     s: &SeState,
     mut res: SeState,
@@ -121,6 +146,8 @@ fn copy_code_to_mem(
 pub fn returndata_copy(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some((mem_addr, ret_addr, size)) = res.pop3() {
+        // copying beyond the returndata halts exceptionally
+        res.push_constraint(le(&add(&ret_addr, &size), &s.returndata_size));
         res.mem = match res.returndata {
             Some(ret) => memcopy(
                 Arc::make_mut(&mut res.memory),
@@ -209,6 +236,27 @@ pub fn mstore8(s: &SeState) -> Vec<(SeState, EdgeType)> {
     vec![]
 }
 
+// the copy reads from the memory before the write, so overlapping ranges behave like memmove
+pub fn mcopy(s: &SeState) -> Vec<(SeState, EdgeType)> {
+    let mut res = s.create_succ();
+    if let Some((mem_addr, src_addr, size)) = res.pop3() {
+        // fix size variable in concrete mode
+        if s.config().concrete_copy {
+            res.push_constraint(le(&size, &const_usize(256)));
+        }
+        res.mem = memcopy(
+            Arc::make_mut(&mut res.memory),
+            s.mem,
+            s.mem,
+            &mem_addr,
+            &src_addr,
+            &size,
+        );
+        return vec![(res, edge_exec())];
+    }
+    vec![]
+}
+
 pub fn extract_mapping_key(memory: &SymbolicMemory, val: &BVal) -> Option<BVal> {
     match val.val() {
         Val256::FSHA3(mem, offset, len) => {
@@ -273,6 +321,7 @@ pub fn storage_load(s: &SeState) -> Vec<(SeState, EdgeType)> {
             }
 
             if let Some(key) = mapping_key {
+                res.record_mapping_read(&key);
                 {
                     if let Some(mem) = res.account().mappings.get(&key) {
                         debug!("Load from mapping: {:?}", key);
@@ -326,7 +375,18 @@ pub fn sstore(s: &SeState) -> Vec<(SeState, EdgeType)> {
         return vec![];
     }
     let mut res = s.create_succ();
+    res.record_privileged_operation(s.pc);
+    res.record_effect(Effect::SStore);
     if let Some((addr, val)) = res.pop2() {
+        if s.flags.contains(Flags::BLOCK_DEPENDENCE)
+            || block_dependent(s, &addr)
+            || block_dependent(s, &val)
+        {
+            res.record_block_dependence(BlockDependentEffect::Store {
+                pc: s.pc,
+                slot: Arc::clone(&addr),
+            });
+        }
         let mut mapping_key = None;
         if cfg!(feature = "keccak") {
             match addr.val() {
@@ -374,6 +434,9 @@ pub fn sstore(s: &SeState) -> Vec<(SeState, EdgeType)> {
                 return vec![(res, edge_exec())];
             }
         }
+        if !FVal::is_constant(&addr) {
+            res.record_storage_write(&addr);
+        }
         let new_storage = word_write(
             Arc::make_mut(&mut res.memory),
             s.account().storage,
@@ -386,6 +449,56 @@ pub fn sstore(s: &SeState) -> Vec<(SeState, EdgeType)> {
     vec![]
 }
 
+pub fn transient_load(s: &SeState) -> Vec<(SeState, EdgeType)> {
+    let mut res = s.create_succ();
+    if let Some(addr) = res.stack.pop() {
+        let load = sload(&s.memory, s.account().transient, &addr);
+        res.record_read(&load);
+        res.push(load);
+        return vec![(res, edge_exec())];
+    }
+    vec![]
+}
+
+pub fn tstore(s: &SeState) -> Vec<(SeState, EdgeType)> {
+    if s.flags.contains(Flags::STATIC) {
+        warn!("State changing tstore operation during static call, dropping path!");
+        return vec![];
+    }
+    let mut res = s.create_succ();
+    if let Some((addr, val)) = res.pop2() {
+        let new_transient = word_write(
+            Arc::make_mut(&mut res.memory),
+            s.account().transient,
+            &addr,
+            &val,
+        );
+        res.account_mut().transient = new_transient;
+        return vec![(res, edge_exec())];
+    }
+    vec![]
+}
+
+pub fn log(s: &SeState, n: usize) -> Vec<(SeState, EdgeType)> {
+    if s.flags.contains(Flags::STATIC) {
+        warn!("State changing log operation during static call, dropping path!");
+        return vec![];
+    }
+    let mut res = s.create_succ();
+    if let Some((offset, size)) = res.pop2() {
+        let mut topics = Vec::with_capacity(n);
+        for _ in 0..n {
+            match res.stack.pop() {
+                Some(topic) => topics.push(topic),
+                None => return vec![],
+            }
+        }
+        res.log(topics, offset, size);
+        return vec![(res, edge_exec())];
+    }
+    vec![]
+}
+
 pub fn revert(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some((addr, size)) = res.pop2() {
@@ -395,19 +508,27 @@ pub fn revert(s: &SeState) -> Vec<(SeState, EdgeType)> {
 
         let loaded_returndata =
             FVal::as_bigint(&mload(&s.memory, s.mem, &addr)).unwrap_or_default();
-        let code = FVal::as_bigint(&mload8(&s.memory, s.mem, &add(&addr, &const_usize(35))))
-            .unwrap_or_default();
 
-        // Detecting failed asserts in solc >= 0.8 based on returndata
+        // Detecting failed asserts and other panics in solc >= 0.8 based on returndata
         if loaded_returndata
             == U256::from_dec_str(
                 // 0x4e487b710
                 "35408467139433450592217433187231851964531694900788300625387963629091585785856",
             )
             .unwrap()
-            && code == FVal::as_bigint(&one()).unwrap_or_default()
         {
-            res.failed_assert = true;
+            let code = mload(&s.memory, s.mem, &add(&addr, &const_usize(4)));
+            if let Some(code) = FVal::as_usize(&code) {
+                res.panic_code = Some(code);
+                res.failed_assert = s.config().panic_codes.contains(&code);
+            }
+        }
+
+        if let Some(size) = FVal::as_usize(&size) {
+            res.revert_reason = RevertReason::decode(size, |i| {
+                FVal::as_usize(&mload8(&s.memory, s.mem, &add(&addr, &const_usize(i))))
+                    .map(|b| b as u8)
+            });
         }
         return vec![(res, edge_terminal())];
     }
@@ -614,6 +735,251 @@ mod tests {
         assert_eq!(state.halting_reason, Some(HaltingReason::Revert),);
     }
 
+    #[test]
+    fn panic_code_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let mut selector = vec![0x4e, 0x48, 0x7b, 0x71];
+        selector.resize(32, 0);
+        let ins = vec![
+            Instr::IPush(selector),   // Panic(uint256)
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IMStore,
+            Instr::IPush(vec![0x12]), // division by zero
+            Instr::IPush(vec![0x04]), // addr
+            Instr::IMStore,
+            Instr::IPush(vec![0x24]), // size
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IRevert,
+        ];
+        let g = generate_test_graph(ins.clone());
+
+        // only assert failures are reported by default
+        let state = &g.get_state_by_id(10);
+        assert_eq!(state.halting_reason, Some(HaltingReason::Revert));
+        assert_eq!(Some(0x12), state.panic_code);
+        assert!(!state.failed_assert);
+
+        CONFIG.write().unwrap().panic_codes = vec![0x01, 0x12];
+        let g = generate_test_graph(ins);
+        let state = &g.get_state_by_id(10);
+        assert_eq!(Some(0x12), state.panic_code);
+        assert!(state.failed_assert);
+        CONFIG.write().unwrap().panic_codes = vec![0x01];
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
+    #[test]
+    fn revert_reason_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let mut selector = vec![0x08, 0xc3, 0x79, 0xa0];
+        selector.resize(32, 0);
+        let mut msg = b"owner".to_vec();
+        msg.resize(32, 0);
+        let ins = vec![
+            Instr::IPush(selector),   // Error(string)
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IMStore,
+            Instr::IPush(vec![0x20]), // offset
+            Instr::IPush(vec![0x04]), // addr
+            Instr::IMStore,
+            Instr::IPush(vec![0x05]), // length
+            Instr::IPush(vec![0x24]), // addr
+            Instr::IMStore,
+            Instr::IPush(msg),        // message
+            Instr::IPush(vec![0x44]), // addr
+            Instr::IMStore,
+            Instr::IPush(vec![0x64]), // size
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IRevert,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(16);
+        assert_eq!(state.halting_reason, Some(HaltingReason::Revert));
+        assert_eq!(
+            Some(RevertReason::Error(String::from("owner"))),
+            state.revert_reason
+        );
+        assert_eq!(None, state.panic_code);
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
+    #[test]
+    fn mcopy_overlapping_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let ins = vec![
+            Instr::IPush(vec![0xff]), // value
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IMStore8,
+            Instr::IPush(vec![0x02]), // size
+            Instr::IPush(vec![0x00]), // src
+            Instr::IPush(vec![0x01]), // dst
+            Instr::IMCopy,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(8);
+        let load = |addr| FVal::as_usize(&mload8(&state.memory, state.mem, &const_usize(addr)));
+        assert_eq!(Some(0xff), load(0));
+        assert_eq!(Some(0xff), load(1));
+        // the source is read before the copy is written
+        assert_eq!(Some(0x00), load(2));
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
+    #[test]
+    fn code_copy_symbolic_size_test() {
+        let ins = vec![
+            Instr::ICallValue,        // size
+            Instr::IPush(vec![0x00]), // code offset
+            Instr::IPush(vec![0x00]), // mem addr
+            Instr::ICodeCopy,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(5);
+        match state.memory[state.mem].op {
+            MemoryOperation::Memcopy { ref size, .. } => {
+                assert_eq!(&state.input_tx().callvalue, size)
+            }
+            _ => panic!("Symbolic code copy should be encoded as memcopy"),
+        }
+    }
+
+    #[test]
+    fn transient_storage_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let ins = vec![
+            Instr::IPush(vec![0x2a]), // value
+            Instr::IPush(vec![0x01]), // addr
+            Instr::ITStore,
+            Instr::IPush(vec![0x01]), // addr
+            Instr::ITLoad,
+            Instr::IPush(vec![0x00]), // size
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IRevert,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(6);
+        assert_eq!(Some(0x2a), FVal::as_usize(&state.stack[0]));
+        assert_eq!(
+            g.get_state_by_id(1).account().storage,
+            state.account().storage
+        );
+
+        // reverting discards the transient write
+        let state = &g.get_state_by_id(9);
+        assert_eq!(state.halting_reason, Some(HaltingReason::Revert));
+        assert_eq!(
+            g.get_state_by_id(1).account().transient,
+            state.account().transient
+        );
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
+    #[test]
+    fn log_test() {
+        CONFIG.write().unwrap().concrete_load = true;
+        let ins = vec![
+            Instr::IPush(vec![0x2a]), // value
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IMStore,
+            Instr::IPush(vec![0xbb]), // topic 1
+            Instr::IPush(vec![0xaa]), // topic 0
+            Instr::IPush(vec![0x20]), // size
+            Instr::IPush(vec![0x00]), // offset
+            Instr::ILog(2),
+            Instr::IPush(vec![0x00]), // size
+            Instr::IPush(vec![0x00]), // addr
+            Instr::IRevert,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(9);
+        assert_eq!(1, state.logs.len());
+        let log = &state.logs[0];
+        assert_eq!(vec![const_usize(0xaa), const_usize(0xbb)], log.topics);
+        assert_eq!(
+            Some(0x2a),
+            FVal::as_usize(&mload(&state.memory, log.mem, &log.offset))
+        );
+
+        // reverting discards the log
+        let state = &g.get_state_by_id(12);
+        assert_eq!(state.halting_reason, Some(HaltingReason::Revert));
+        assert!(state.logs.is_empty());
+        CONFIG.write().unwrap().concrete_load = false;
+    }
+
+    #[test]
+    fn storage_write_test() {
+        let ins = vec![
+            Instr::IPush(vec![0x01]), // value
+            Instr::ICaller,           // symbolic slot
+            Instr::ISStore,
+            Instr::IPush(vec![0x02]), // value
+            Instr::IPush(vec![0x00]), // concrete slot
+            Instr::ISStore,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(7);
+        assert_eq!(
+            vec![(state.account, Arc::clone(&state.input_tx().caller))],
+            *state.storage_writes
+        );
+    }
+
+    #[test]
+    fn effect_test() {
+        let ins = vec![
+            Instr::IPush(vec![0x01]), // value
+            Instr::IPush(vec![0x00]), // addr
+            Instr::ISStore,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(3);
+        assert!(state.effects.is_empty());
+
+        let state = &g.get_state_by_id(4);
+        assert!(state.effects.contains(&(state.account, Effect::SStore)));
+        assert_eq!(1, state.effects.len());
+    }
+
+    #[test]
+    fn block_dependence_test() {
+        let ins = vec![
+            Instr::ICaller,           // value
+            Instr::IPush(vec![0x00]), // addr
+            Instr::ISStore,
+            Instr::ITimeStamp,        // value
+            Instr::IPush(vec![0x01]), // addr
+            Instr::ISStore,
+            Instr::IPush(vec![0x01]), // number
+            Instr::IBlockHash,        // value
+            Instr::IPush(vec![0x02]), // addr
+            Instr::ISStore,
+        ];
+        let g = generate_test_graph(ins);
+
+        let state = &g.get_state_by_id(4);
+        assert!(state.block_dependence.is_empty());
+
+        let state = &g.get_state_by_id(7);
+        assert_eq!(
+            vec![BlockDependentEffect::Store {
+                pc: 7,
+                slot: const_usize(0x01)
+            }],
+            *state.block_dependence
+        );
+
+        let state = &g.get_state_by_id(11);
+        assert_eq!(1, state.block_dependence.len());
+    }
+
     // 0x165B98160458E9698bF85E29ED09e8c6e3dDDc85
     #[test]
     #[ignore]
@@ -649,6 +1015,7 @@ mod tests {
         let inital_tx = env.new_attacker_tx(&mut memory, attacker, victim);
 
         let initial_storage = env.get_account(&victim).storage;
+        let initial_transient = env.get_account(&victim).transient;
         let dasm = Disasm::new(ins);
         let config = CONFIG.read().unwrap().clone();
 
@@ -657,6 +1024,7 @@ mod tests {
             config,
             dasm,
             initial_storage,
+            initial_transient,
             Solvers::Yice {
                 count: num_cpus::get(),
                 timeout: 120_000,
@@ -701,6 +1069,7 @@ mod tests {
         let inital_tx = env.new_attacker_tx(&mut memory, attacker, victim);
 
         let initial_storage = env.get_account(&victim).storage;
+        let initial_transient = env.get_account(&victim).transient;
         let dasm = Disasm::new(ins);
         let config = CONFIG.read().unwrap().clone();
 
@@ -708,6 +1077,7 @@ mod tests {
             config,
             dasm,
             initial_storage,
+            initial_transient,
             Solvers::Yice {
                 count: num_cpus::get(),
                 timeout: 120_000,
@@ -771,6 +1141,7 @@ mod tests {
         let inital_tx = env.new_attacker_tx(&mut memory, attacker, victim);
 
         let initial_storage = env.get_account(&victim).storage;
+        let initial_transient = env.get_account(&victim).transient;
         let dasm = Disasm::new(ins);
         let config = CONFIG.read().unwrap().clone();
 
@@ -778,6 +1149,7 @@ mod tests {
             config,
             dasm,
             initial_storage,
+            initial_transient,
             Solvers::Yice {
                 count: num_cpus::get(),
                 timeout: 120_000,
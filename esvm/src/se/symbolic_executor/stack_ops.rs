@@ -1,9 +1,11 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use crate::bytecode::Instr;
 use crate::se::{
     expr::bval::*,
     symbolic_edge::{edge_exec, edge_terminal, EdgeType},
-    symbolic_state::{HaltingReason, SeState},
+    symbolic_state::{Flags, HaltingReason, Overflow, SeState},
 };
 
 pub fn stop(s: &SeState) -> Vec<(SeState, EdgeType)> {
@@ -105,7 +107,85 @@ pub fn blockhash(s: &SeState) -> Vec<(SeState, EdgeType)> {
     vec![]
 }
 
-// Only support constant atm
+/// Checks if a value is derived from block values, which are predictable or miner controlled,
+/// the blockhash is left out since the concrete replay cannot reproduce it
+pub fn block_dependent(s: &SeState, val: &BVal) -> bool {
+    let sources: Vec<&BVal> = s
+        .env
+        .blocks
+        .iter()
+        .flat_map(|b| {
+            [
+                &b.timestamp,
+                &b.number,
+                &b.coinbase,
+                &b.prevrandao,
+            ]
+        })
+        .collect();
+    depends_on(s, val, &sources)
+}
+
+/// Checks if any of the sources is part of a value, looking through loads and hashed memory
+pub fn depends_on(s: &SeState, val: &BVal, sources: &[&BVal]) -> bool {
+    let mut visited = HashSet::new();
+    let mut todo = vec![Arc::clone(val)];
+    while let Some(v) = todo.pop() {
+        if !visited.insert(Arc::as_ptr(&v)) {
+            continue;
+        }
+        if sources.contains(&&v) {
+            return true;
+        }
+        match v.val() {
+            Val256::FAdd(a, b)
+            | Val256::FSub(a, b)
+            | Val256::FMul(a, b)
+            | Val256::FDiv(a, b)
+            | Val256::FSDiv(a, b)
+            | Val256::FMod(a, b)
+            | Val256::FSMod(a, b)
+            | Val256::FExp(a, b)
+            | Val256::FLt(a, b)
+            | Val256::FSLt(a, b)
+            | Val256::FLe(a, b)
+            | Val256::FEql(a, b)
+            | Val256::FNEql(a, b)
+            | Val256::FImplies(a, b)
+            | Val256::FAnd(a, b)
+            | Val256::FOr(a, b)
+            | Val256::FXor(a, b)
+            | Val256::FByteAt(a, b)
+            | Val256::FByteExtract(a, b)
+            | Val256::FShl(a, b)
+            | Val256::FAShr(a, b)
+            | Val256::FLShr(a, b) => {
+                todo.push(Arc::clone(a));
+                todo.push(Arc::clone(b));
+            }
+            Val256::FITE(c, a, b) => {
+                todo.push(Arc::clone(c));
+                todo.push(Arc::clone(a));
+                todo.push(Arc::clone(b));
+            }
+            Val256::FNot(a) | Val256::FMLoad(_, a) | Val256::FSLoad(_, a) => {
+                todo.push(Arc::clone(a))
+            }
+            Val256::FCombine32(vals) => todo.extend(vals.iter().cloned()),
+            // hashed memory, e.g. keccak256(abi.encodePacked(block.timestamp))
+            Val256::FSHA3(mem, offset, len) => {
+                if let Some(len) = FVal::as_usize(len) {
+                    for i in (0..len.min(0x100)).step_by(32) {
+                        todo.push(mload(&s.memory, *mem, &add(offset, &const_usize(i))));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
 pub fn exponentiation(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some((base, exponent)) = res.pop2() {
@@ -114,12 +194,125 @@ pub fn exponentiation(s: &SeState) -> Vec<(SeState, EdgeType)> {
                 res.push(exp(&base, &exponent));
                 return vec![(res, edge_exec())];
             }
-            _ => warn!("Not supported symbolic exponentiation, dropping path"),
+            (None, Some(e)) => {
+                res.push(square_and_multiply(&base, e));
+                return vec![(res, edge_exec())];
+            }
+            _ => return split_exponent(res, &base, &exponent),
         }
     }
     vec![]
 }
 
+fn square_and_multiply(base: &BVal, mut exponent: usize) -> BVal {
+    let mut result = one();
+    let mut square = Arc::clone(base);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul(&result, &square);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            square = mul(&square, &square);
+        }
+    }
+    result
+}
+
+// case split on all exponents up to the configured bound, anything larger gets overapproximated
+// and marked as unsupported
+fn split_exponent(res: SeState, base: &BVal, exponent: &BVal) -> Vec<(SeState, EdgeType)> {
+    let bound = res.config().exp_bound;
+    let mut states = vec![];
+    for e in 0..=bound {
+        let mut fork = res.fork();
+        fork.push_constraint(eql(exponent, &const_usize(e)));
+        if !fork.check_sat() {
+            continue;
+        }
+        fork.push(square_and_multiply(base, e));
+        states.push((fork, edge_exec()));
+    }
+
+    let mut unbounded = res;
+    unbounded.push_constraint(lt(&const_usize(bound), exponent));
+    if unbounded.check_sat() {
+        warn!(
+            "Symbolic exponent exceeds bound of {}, marking result as unsupported",
+            bound
+        );
+        unbounded.flags |= Flags::UNSUPPORTED_EXP;
+        unbounded.push(fresh_var("exp"));
+        states.push((unbounded, edge_exec()));
+    }
+    states
+}
+
+// values which are persisted or transferred, i.e. where wrapping arithmetic causes damage
+pub fn overflow_sink(s: &SeState, ins: &Instr) -> Option<BVal> {
+    let depth = match ins {
+        Instr::ISStore => 2,
+        Instr::ICall => 3,
+        _ => return None,
+    };
+    s.stack.len().checked_sub(depth).map(|i| Arc::clone(&s.stack[i]))
+}
+
+pub fn record_overflows(transitions: &mut [(SeState, EdgeType)], pc: usize, val: &BVal) {
+    let overflows: Vec<_> = wrap_conditions(val)
+        .into_iter()
+        .map(|condition| Overflow { pc, condition })
+        .collect();
+    if overflows.is_empty() {
+        return;
+    }
+    for (state, _) in transitions.iter_mut() {
+        Arc::make_mut(&mut state.overflows).extend(overflows.iter().cloned());
+    }
+}
+
+// conditions under which an add, sub or mul inside of val wraps around, loads and hashes are
+// treated as opaque values
+fn wrap_conditions(val: &BVal) -> Vec<BVal> {
+    let mut conditions = vec![];
+    let mut todo = vec![val];
+    while let Some(v) = todo.pop() {
+        match v.val() {
+            Val256::FAdd(a, b) => {
+                conditions.push(lt(v, a));
+                todo.push(a);
+                todo.push(b);
+            }
+            Val256::FSub(a, b) => {
+                conditions.push(lt(a, b));
+                todo.push(a);
+                todo.push(b);
+            }
+            Val256::FMul(a, b) => {
+                conditions.push(neql(&ite(&eql(a, &zero()), b, &div(v, a)), b));
+                todo.push(a);
+                todo.push(b);
+            }
+            Val256::FAnd(a, b)
+            | Val256::FOr(a, b)
+            | Val256::FXor(a, b)
+            | Val256::FShl(a, b)
+            | Val256::FLShr(a, b)
+            | Val256::FAShr(a, b) => {
+                todo.push(a);
+                todo.push(b);
+            }
+            Val256::FNot(a) => todo.push(a),
+            Val256::FITE(_, a, b) => {
+                todo.push(a);
+                todo.push(b);
+            }
+            _ => {}
+        }
+    }
+    conditions
+}
+
 pub fn sign_extend(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some((size, value)) = res.pop2() {
@@ -163,11 +356,107 @@ pub fn jump_if(s: &SeState) -> Vec<(SeState, EdgeType)> {
         {
             targets.push(ft);
         }
+        let checks = caller_checked_slots(cond, &s.input_tx().caller);
+        let origin = &s.input_tx().origin;
+        // origin checks only matter if the origin can differ from the caller
+        let origin_check = *origin != s.input_tx().caller
+            && comparisons(cond).iter().any(|(a, b)| {
+                masked_values(a).contains(&origin) || masked_values(b).contains(&origin)
+            });
+        let block_check = block_dependent(s, cond);
+        // a branch on the result of a failed call handles the failure
+        let unchecked_calls: Vec<_> = s
+            .failed_calls
+            .iter()
+            .filter(|call| !depends_on(s, cond, &[&call.result]))
+            .cloned()
+            .collect();
+        let calls_checked = unchecked_calls.len() != s.failed_calls.len();
+        for (t, _) in targets.iter_mut() {
+            if calls_checked {
+                t.failed_calls = Arc::new(unchecked_calls.clone());
+            }
+            if !checks.is_empty() {
+                t.record_caller_checks(&checks);
+            }
+            if origin_check {
+                t.flags |= Flags::ORIGIN_CHECK;
+            }
+            if block_check {
+                t.flags |= Flags::BLOCK_DEPENDENCE;
+            }
+        }
         return targets;
     }
     vec![]
 }
 
+/// Constant storage slots whose value is compared against the caller in a branch condition
+fn caller_checked_slots(cond: &BVal, caller: &BVal) -> Vec<BVal> {
+    let mut slots = vec![];
+    for (a, b) in comparisons(cond) {
+        let (lhs, rhs) = (masked_values(a), masked_values(b));
+        for (x, y) in [(&lhs, &rhs), (&rhs, &lhs)] {
+            if !x.contains(&caller) {
+                continue;
+            }
+            for val in y.iter() {
+                if let Val256::FSLoad(_, slot) = val.val() {
+                    if FVal::is_constant(slot) && !slots.contains(slot) {
+                        slots.push(Arc::clone(slot));
+                    }
+                }
+            }
+        }
+    }
+    slots
+}
+
+/// The operands of all (in)equalities in a branch condition
+fn comparisons(cond: &BVal) -> Vec<(&BVal, &BVal)> {
+    let mut res = vec![];
+    let mut todo = vec![cond];
+    while let Some(v) = todo.pop() {
+        match v.val() {
+            Val256::FEql(a, b) | Val256::FNEql(a, b) => {
+                res.push((a, b));
+                todo.push(a);
+                todo.push(b);
+            }
+            Val256::FAnd(a, b) | Val256::FOr(a, b) => {
+                todo.push(a);
+                todo.push(b);
+            }
+            Val256::FNot(a) => todo.push(a),
+            Val256::FITE(c, a, b) => {
+                todo.push(c);
+                todo.push(a);
+                todo.push(b);
+            }
+            _ => {}
+        }
+    }
+    res
+}
+
+/// The operands of `val` which are only masked or shifted, e.g. `caller` in `caller & mask`
+pub fn masked_values(val: &BVal) -> Vec<&BVal> {
+    match val.val() {
+        Val256::FAnd(a, b)
+        | Val256::FOr(a, b)
+        | Val256::FXor(a, b)
+        | Val256::FShl(a, b)
+        | Val256::FLShr(a, b)
+        | Val256::FAShr(a, b) => {
+            let mut values = masked_values(a);
+            values.append(&mut masked_values(b));
+            values
+        }
+        Val256::FNot(a) => masked_values(a),
+        _ => vec![val],
+    }
+}
+
 pub fn jump(s: &SeState) -> Vec<(SeState, EdgeType)> {
     let mut res = s.create_succ();
     if let Some(ref target) = res.stack.pop() {
@@ -231,6 +520,8 @@ mod tests {
     use super::*;
 
     use crate::bytecode::Instr;
+    use crate::se::config::HIJACK_ADDR_HEX;
+    use crate::se::symbolic_analysis::CONFIG;
     use crate::test_helpers::generate_test_graph;
 
     #[test]
@@ -294,4 +585,182 @@ mod tests {
         let state = &g.get_state_by_id(6);
         assert_eq!(const_usize(0x01), state.stack[4]);
     }
+
+    #[test]
+    fn overflow_test() {
+        CONFIG.write().unwrap().overflow_check = true;
+        let ins = vec![
+            Instr::IPush(vec![0x01]),
+            Instr::ICallValue,
+            Instr::ISub,              // value
+            Instr::IPush(vec![0x00]), // addr
+            Instr::ISStore,
+            Instr::IStop,
+        ];
+        let g = generate_test_graph(ins);
+        CONFIG.write().unwrap().overflow_check = false;
+
+        let state = &g.get_state_by_id(4);
+        assert!(state.overflows.is_empty());
+
+        let state = &g.get_state_by_id(6);
+        assert_eq!(1, state.overflows.len());
+        let mut check = state.clone();
+        assert_eq!(6, state.overflows[0].pc);
+        check.push_constraint(Arc::clone(&state.overflows[0].condition));
+        assert!(check.check_sat());
+    }
+
+    #[test]
+    fn privileged_slot_test() {
+        let ins = vec![
+            Instr::IPush(vec![0x00]), // owner slot
+            Instr::ISLoad,
+            Instr::ICaller,
+            Instr::IEql,
+            Instr::IPush(vec![0x09]), // target
+            Instr::IJumpIf,
+            Instr::IStop,
+            Instr::IJumpDest,
+            Instr::IPush(vec![0x01]), // value
+            Instr::IPush(vec![0x01]), // addr
+            Instr::ISStore,
+            Instr::IStop,
+        ];
+        let g = generate_test_graph(ins);
+
+        let states = g.end_states();
+        assert_eq!(2, states.len());
+        for state in states.iter() {
+            assert_eq!(vec![const_usize(0x00)], *state.caller_checks);
+        }
+        let guarded: Vec<_> = states
+            .iter()
+            .filter(|s| !s.privileged_slots.is_empty())
+            .collect();
+        assert_eq!(1, guarded.len());
+        assert!(
+            guarded[0]
+                .privileged_slots
+                .contains(&(guarded[0].account, const_usize(0x00)))
+        );
+    }
+
+    #[test]
+    fn checked_call_test() {
+        let call = vec![
+            Instr::IPush(vec![0x00]), // out_size
+            Instr::IPush(vec![0x00]), // out_off
+            Instr::IPush(vec![0x00]), // in_size
+            Instr::IPush(vec![0x00]), // in_off
+            Instr::IPush(vec![0x00]), // value
+            Instr::IPush(hexdecode::decode(HIJACK_ADDR_HEX.as_bytes()).unwrap()), // to
+            Instr::IPush(vec![0x08, 0xfc]), // gas
+            Instr::ICall,
+        ];
+
+        let mut ignored = call.clone();
+        ignored.extend(vec![Instr::IPop, Instr::IStop]);
+        let g = generate_test_graph(ignored);
+        assert!(g.end_states().iter().any(|s| !s.failed_calls.is_empty()));
+
+        let mut checked = call;
+        checked.extend(vec![
+            Instr::IPush(vec![0x27]), // target
+            Instr::IJumpIf,
+            Instr::IStop,
+            Instr::IJumpDest,
+            Instr::IStop,
+        ]);
+        let g = generate_test_graph(checked);
+        assert!(g.end_states().iter().all(|s| s.failed_calls.is_empty()));
+    }
+
+    #[test]
+    fn origin_check_test() {
+        CONFIG.write().unwrap().tx_origin = true;
+        let ins = vec![
+            Instr::IPush(vec![0x00]), // owner slot
+            Instr::ISLoad,
+            Instr::IOrigin,
+            Instr::IEql,
+            Instr::IPush(vec![0x09]), // target
+            Instr::IJumpIf,
+            Instr::IStop,
+            Instr::IJumpDest,
+            Instr::IPush(vec![0x01]), // value
+            Instr::IPush(vec![0x01]), // addr
+            Instr::ISStore,
+            Instr::IStop,
+        ];
+        let g = generate_test_graph(ins);
+        CONFIG.write().unwrap().tx_origin = false;
+
+        let states = g.end_states();
+        assert_eq!(2, states.len());
+        assert!(states.iter().all(|s| s.flags.contains(Flags::ORIGIN_CHECK)));
+        assert!(states.iter().all(|s| s.caller_checks.is_empty()));
+        assert_eq!(
+            1,
+            states
+                .iter()
+                .filter(|s| s.flags.contains(Flags::ORIGIN_AUTH))
+                .count()
+        );
+        let auth = states
+            .iter()
+            .find(|s| s.flags.contains(Flags::ORIGIN_AUTH))
+            .unwrap();
+        assert_eq!(vec![(auth.account, 14)], *auth.origin_guarded_ops);
+    }
+
+    #[test]
+    fn push0_selfbalance_test() {
+        let ins = vec![Instr::IPush(vec![]), Instr::ISelfBalance];
+
+        let g = generate_test_graph(ins);
+        let state = &g.get_state_by_id(2);
+        assert_eq!(const_usize(0x00), state.stack[0]);
+        let state = &g.get_state_by_id(3);
+        assert_eq!(state.account().balance, state.stack[1]);
+    }
+
+    #[test]
+    fn blobhash_test() {
+        let ins = vec![
+            Instr::IPush(vec![]),
+            Instr::IBlobHash,
+            Instr::IPush(vec![0x01]),
+            Instr::IBlobHash,
+        ];
+
+        // only the first blob is carried by default
+        let g = generate_test_graph(ins);
+        let state = &g.get_state_by_id(5);
+        assert_eq!(state.env.latest_block().blobhashes[0], state.stack[0]);
+        assert_eq!(zero(), state.stack[1]);
+    }
+
+    #[test]
+    fn exponentiation_test() {
+        CONFIG.write().unwrap().exp_bound = 2;
+        let ins = vec![
+            Instr::ICallValue,        // exponent
+            Instr::IPush(vec![0x0a]), // base
+            Instr::IExp,
+            Instr::IStop,
+        ];
+
+        let g = generate_test_graph(ins);
+        let states = g.end_states();
+        assert_eq!(4, states.len());
+        let mut results: Vec<_> = states
+            .iter()
+            .filter(|s| !s.flags.contains(Flags::UNSUPPORTED_EXP))
+            .map(|s| FVal::as_usize(&s.stack[0]).unwrap())
+            .collect();
+        results.sort();
+        assert_eq!(vec![1, 10, 100], results);
+        CONFIG.write().unwrap().exp_bound = 18;
+    }
 }
@@ -8,6 +8,7 @@ use ena::unify::{InPlaceUnificationTable, NoError, UnificationTable, UnifyKey, U
 
 use crate::bytecode::Instr;
 use crate::se::{
+    config::{KNOWN_SIGNATURE, SECP256K1_ORDER},
     env::{fresh_var_name, Account, AccountId, Env, Transaction, TxId, GLOBAL_COVERAGE_MAP},
     expr::{
         bval::*,
@@ -26,6 +27,10 @@ bitflags! {
         const HIJACK_CONTROL_FLOW = 0b0000_0010;
         const STATIC = 0b1000_0000;
         const FAILURE = 0b0000_0100;
+        const UNSUPPORTED_EXP = 0b0000_1000;
+        const ORIGIN_CHECK = 0b0001_0000;
+        const ORIGIN_AUTH = 0b0010_0000;
+        const BLOCK_DEPENDENCE = 0b0100_0000;
         const NON_STATIC_MASK = 0b0111_1111;
 
         // precompiled contracts
@@ -49,6 +54,176 @@ pub enum HaltingReason {
     Invalid,
 }
 
+/// A decoded revert reason, panics are tracked separately as panic codes
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RevertReason {
+    /// Error(string), i.e. require messages
+    Error(String),
+    /// A custom error identified by its selector
+    Custom(u32),
+}
+
+impl RevertReason {
+    /// Decodes revert data of the given size, byte returns None for bytes which are not concrete
+    pub fn decode<F>(size: usize, byte: F) -> Option<Self>
+    where
+        F: Fn(usize) -> Option<u8>,
+    {
+        if size < 4 {
+            return None;
+        }
+        // abi encoded word which has to fit into an usize
+        let word = |offset: usize| -> Option<usize> {
+            let mut val = 0usize;
+            for i in 0..32 {
+                let b = byte(offset.checked_add(i)?)?;
+                if i < 24 && b != 0 {
+                    return None;
+                }
+                val = (val << 8) | b as usize;
+            }
+            Some(val)
+        };
+        let selector = (0..4).try_fold(0u32, |acc, i| Some((acc << 8) | byte(i)? as u32))?;
+        match selector {
+            // Error(string)
+            0x08c3_79a0 => {
+                let offset = word(4)?.checked_add(4)?;
+                let len = word(offset)?;
+                let start = offset.checked_add(32)?;
+                if start.checked_add(len)? > size {
+                    return None;
+                }
+                let msg = (start..start + len).map(&byte).collect::<Option<Vec<u8>>>()?;
+                Some(RevertReason::Error(String::from_utf8_lossy(&msg).into_owned()))
+            }
+            // Panic(uint256)
+            0x4e48_7b71 => None,
+            selector => Some(RevertReason::Custom(selector)),
+        }
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RevertReason::Error(msg) => write!(f, "Error({:?})", msg),
+            RevertReason::Custom(selector) => write!(f, "custom error {:#010x}", selector),
+        }
+    }
+}
+
+/// A log emitted during execution, the data is the memory slice at the time of the LOG
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+    pub account: AccountId,
+    pub tx: TxId,
+    pub topics: Vec<BVal>,
+    pub mem: MVal,
+    pub offset: BVal,
+    pub size: BVal,
+}
+
+/// A value transfer or storage write which depends on block values
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockDependentEffect {
+    Transfer { pc: usize, value: BVal },
+    Store { pc: usize, slot: BVal },
+}
+
+/// Arithmetic which may wrap around before its result is stored or sent
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overflow {
+    /// The SSTORE or CALL persisting the result
+    pub pc: usize,
+    pub condition: BVal,
+}
+
+/// A state changing operation of an account
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Effect {
+    SStore,
+    ValueTransfer,
+    Selfdestruct,
+    DelegateCall,
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Effect::SStore => write!(f, "SSTORE"),
+            Effect::ValueTransfer => write!(f, "CALL with value"),
+            Effect::Selfdestruct => write!(f, "SELFDESTRUCT"),
+            Effect::DelegateCall => write!(f, "DELEGATECALL"),
+        }
+    }
+}
+
+/// A call of the ecrecover precompile, the signer is a fresh value kept consistent with all
+/// other calls on the path
+#[derive(Clone, Debug, PartialEq)]
+pub struct EcRecover {
+    pub hash: BVal,
+    pub v: BVal,
+    pub r: BVal,
+    pub s: BVal,
+    pub signer: BVal,
+}
+
+impl EcRecover {
+    /// Both calls recover the same signature over the same hash
+    pub fn same_signature(&self, other: &EcRecover) -> BVal {
+        and(
+            &and(&eql(&self.hash, &other.hash), &eql(&self.v, &other.v)),
+            &and(&eql(&self.r, &other.r), &eql(&self.s, &other.s)),
+        )
+    }
+
+    /// The other call recovers the malleable counterpart (r, n - s) with flipped v of this
+    /// signature, which yields the same signer
+    pub fn twin_signature(&self, other: &EcRecover) -> BVal {
+        let flipped_v = sub(&const_usize(55), &self.v);
+        let flipped_s = sub(&const256(SECP256K1_ORDER), &self.s);
+        and(
+            &and(&eql(&self.hash, &other.hash), &eql(&flipped_v, &other.v)),
+            &and(&eql(&self.r, &other.r), &eql(&flipped_s, &other.s)),
+        )
+    }
+
+    /// This call recovers a known signature, so a concrete replay recovers the same signer
+    pub fn known_signature(&self) -> BVal {
+        let [hash, v, r, s, signer] = KNOWN_SIGNATURE;
+        and(
+            &and(
+                &and(
+                    &eql(&self.hash, &const256(hash)),
+                    &eql(&self.v, &const256(v)),
+                ),
+                &and(&eql(&self.r, &const256(r)), &eql(&self.s, &const256(s))),
+            ),
+            &eql(&self.signer, &const256(signer)),
+        )
+    }
+}
+
+/// A delegate call which may overwrite a slot the caller uses as implementation or admin pointer
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageCollision {
+    pub pc: usize,
+    pub slot: BVal,
+    /// Holds if the slot no longer contains its value from before the call
+    pub overwritten: BVal,
+}
+
+/// An external call on the current path which was assumed to fail
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailedCall {
+    pub pc: usize,
+    pub callee: AccountId,
+    /// The value the call pushed, i.e. zero
+    pub result: BVal,
+}
+
 #[derive(Clone, Debug)]
 pub struct ResultState {
     /// The content of the stack
@@ -86,6 +261,12 @@ pub struct ResultState {
     /// The flag indicating that an assertion can fail in the path
     pub failed_assert: bool,
 
+    /// The Solidity panic code if the path reverted with Panic(uint256)
+    pub panic_code: Option<usize>,
+
+    /// The decoded revert data if the path reverted with an error
+    pub revert_reason: Option<RevertReason>,
+
     pub call_depth: usize,
 
     pub old_memory: Arc<HashSet<MVal>>,
@@ -95,6 +276,9 @@ pub struct ResultState {
     pub solver_pool: Arc<SolverPool>,
     pub keccaks: Arc<HashSet<BVal>>,
     pub constraints_tracker: Arc<ConstraintSetSplitter>,
+    pub logs: Arc<Vec<LogRecord>>,
+    pub privileged_slots: Arc<HashSet<(AccountId, BVal)>>,
+    pub ecrecovers: Arc<Vec<EcRecover>>,
 }
 
 // rc for everything that does not change each state
@@ -145,6 +329,12 @@ pub struct SeState {
     /// The flag indicating that an assertion can fail in the path
     pub failed_assert: bool,
 
+    /// The Solidity panic code if the path reverted with Panic(uint256)
+    pub panic_code: Option<usize>,
+
+    /// The decoded revert data if the path reverted with an error
+    pub revert_reason: Option<RevertReason>,
+
     /// The counter for the call depth
     pub call_depth: usize,
 
@@ -169,6 +359,45 @@ pub struct SeState {
     /// Variable Tracker for easier constraint handling
     pub constraints_tracker: Arc<ConstraintSetSplitter>,
 
+    /// All logs emitted so far, including previous transactions
+    pub logs: Arc<Vec<LogRecord>>,
+    /// Number of logs emitted before this execution context, later logs are dropped on revert
+    log_checkpoint: usize,
+
+    /// Wrap-around conditions of arithmetic results which were stored or sent in this context
+    pub overflows: Arc<Vec<Overflow>>,
+
+    /// Storage writes to symbolic slots in this context
+    pub storage_writes: Arc<Vec<(AccountId, BVal)>>,
+
+    /// Value transfers and storage writes depending on block values in this context
+    pub block_dependence: Arc<Vec<BlockDependentEffect>>,
+
+    /// External calls which failed on this path in this context
+    pub failed_calls: Arc<Vec<FailedCall>>,
+
+    /// Pointer slots possibly overwritten by delegate calls in this context
+    pub storage_collisions: Arc<Vec<StorageCollision>>,
+
+    /// Storage slots compared against the caller on this path in this context
+    pub caller_checks: Arc<Vec<BVal>>,
+
+    /// Storage slots guarding a state changing operation, i.e. inferred access control variables
+    pub privileged_slots: Arc<HashSet<(AccountId, BVal)>>,
+
+    /// State changing operations guarded by a check against the origin in this transaction, as
+    /// the account and program counter executing them
+    pub origin_guarded_ops: Arc<Vec<(AccountId, usize)>>,
+
+    /// Mapping positions loaded from on this path in this context
+    pub mapping_reads: Arc<HashSet<(AccountId, BVal)>>,
+
+    /// State changing operations on this path in this context
+    pub effects: Arc<HashSet<(AccountId, Effect)>>,
+
+    /// Calls of the ecrecover precompile on this path, including previous transactions
+    pub ecrecovers: Arc<Vec<EcRecover>>,
+
     // loop detection
     last_addrs: Arc<VecDeque<BVal>>,
     addrs_counter: Arc<HashMap<BVal, usize>>,
@@ -215,6 +444,7 @@ impl SeState {
         let env = Arc::clone(env);
         let keccaks = Arc::new(HashSet::new());
         let constraints_tracker = Arc::new(ConstraintSetSplitter::new());
+        let logs = Arc::new(vec![]);
 
         SeState {
             id,
@@ -235,11 +465,26 @@ impl SeState {
             call_depth,
             halting_reason,
             failed_assert,
+            panic_code: None,
+            revert_reason: None,
             old_memory,
             flags: Default::default(),
             context,
             keccaks,
             constraints_tracker,
+            logs,
+            log_checkpoint: 0,
+            overflows: Arc::new(vec![]),
+            storage_writes: Arc::new(vec![]),
+            failed_calls: Arc::new(vec![]),
+            block_dependence: Arc::new(vec![]),
+            storage_collisions: Arc::new(vec![]),
+            caller_checks: Arc::new(vec![]),
+            privileged_slots: Arc::new(HashSet::new()),
+            origin_guarded_ops: Arc::new(vec![]),
+            mapping_reads: Arc::new(HashSet::new()),
+            effects: Arc::new(HashSet::new()),
+            ecrecovers: Arc::new(vec![]),
         }
     }
 
@@ -282,6 +527,16 @@ impl SeState {
         // tracker
         new_state.constraints_tracker = Arc::clone(&s.constraints_tracker);
 
+        // logs
+        new_state.log_checkpoint = s.logs.len();
+        new_state.logs = s.logs;
+
+        // access control slots inferred so far
+        new_state.privileged_slots = s.privileged_slots;
+
+        // signatures recovered so far
+        new_state.ecrecovers = s.ecrecovers;
+
         new_state
     }
 
@@ -313,12 +568,27 @@ impl SeState {
             call_depth: self.call_depth,
             halting_reason: self.halting_reason.clone(),
             failed_assert: self.failed_assert.clone(),
+            panic_code: self.panic_code,
+            revert_reason: self.revert_reason.clone(),
             old_memory: self.old_memory.clone(),
             flags: self.flags,
             context: Arc::clone(&self.context),
             keccaks: Arc::clone(&self.keccaks),
             memory: Arc::clone(&self.memory),
             constraints_tracker: Arc::clone(&self.constraints_tracker),
+            logs: Arc::clone(&self.logs),
+            log_checkpoint: self.log_checkpoint,
+            overflows: Arc::clone(&self.overflows),
+            storage_writes: Arc::clone(&self.storage_writes),
+            failed_calls: Arc::clone(&self.failed_calls),
+            block_dependence: Arc::clone(&self.block_dependence),
+            storage_collisions: Arc::clone(&self.storage_collisions),
+            caller_checks: Arc::clone(&self.caller_checks),
+            privileged_slots: Arc::clone(&self.privileged_slots),
+            origin_guarded_ops: Arc::clone(&self.origin_guarded_ops),
+            mapping_reads: Arc::clone(&self.mapping_reads),
+            effects: Arc::clone(&self.effects),
+            ecrecovers: Arc::clone(&self.ecrecovers),
         }
     }
 
@@ -342,12 +612,27 @@ impl SeState {
             call_depth: self.call_depth,
             halting_reason: self.halting_reason.clone(),
             failed_assert: self.failed_assert.clone(),
+            panic_code: self.panic_code,
+            revert_reason: self.revert_reason.clone(),
             old_memory: self.old_memory.clone(),
             flags: self.flags,
             context: Arc::clone(&self.context),
             keccaks: Arc::clone(&self.keccaks),
             memory: Arc::clone(&self.memory),
             constraints_tracker: Arc::clone(&self.constraints_tracker),
+            logs: Arc::clone(&self.logs),
+            log_checkpoint: self.log_checkpoint,
+            overflows: Arc::clone(&self.overflows),
+            storage_writes: Arc::clone(&self.storage_writes),
+            failed_calls: Arc::clone(&self.failed_calls),
+            block_dependence: Arc::clone(&self.block_dependence),
+            storage_collisions: Arc::clone(&self.storage_collisions),
+            caller_checks: Arc::clone(&self.caller_checks),
+            privileged_slots: Arc::clone(&self.privileged_slots),
+            origin_guarded_ops: Arc::clone(&self.origin_guarded_ops),
+            mapping_reads: Arc::clone(&self.mapping_reads),
+            effects: Arc::clone(&self.effects),
+            ecrecovers: Arc::clone(&self.ecrecovers),
         }
     }
 
@@ -365,6 +650,8 @@ impl SeState {
             returndata_size: Arc::clone(&self.returndata_size),
             halting_reason: self.halting_reason.clone(),
             failed_assert: self.failed_assert.clone(),
+            panic_code: self.panic_code,
+            revert_reason: self.revert_reason.clone(),
             old_memory: Arc::clone(&self.old_memory),
             reads: Arc::clone(&self.reads),
             previous_tx: self.previous_tx.clone(),
@@ -372,6 +659,9 @@ impl SeState {
             solver_pool: self.context.solver_pool(),
             keccaks: Arc::clone(&self.keccaks),
             constraints_tracker: Arc::clone(&self.constraints_tracker),
+            logs: Arc::clone(&self.logs),
+            privileged_slots: Arc::clone(&self.privileged_slots),
+            ecrecovers: Arc::clone(&self.ecrecovers),
         }
     }
 
@@ -480,6 +770,23 @@ impl SeState {
         let old_stor = self.account().storage;
         Arc::make_mut(&mut self.old_memory).insert(old_stor);
         self.account_mut().storage = self.context.initial_storage();
+        let old_transient = self.account().transient;
+        Arc::make_mut(&mut self.old_memory).insert(old_transient);
+        self.account_mut().transient = self.context.initial_transient();
+        let checkpoint = self.log_checkpoint;
+        Arc::make_mut(&mut self.logs).truncate(checkpoint);
+    }
+
+    pub fn log(&mut self, topics: Vec<BVal>, offset: BVal, size: BVal) {
+        let record = LogRecord {
+            account: self.account,
+            tx: self.input_tx,
+            topics,
+            mem: self.mem,
+            offset,
+            size,
+        };
+        Arc::make_mut(&mut self.logs).push(record);
     }
 
     pub fn get_instruction(&self) -> Option<Instr> {
@@ -502,6 +809,70 @@ impl SeState {
         Arc::make_mut(&mut self.keccaks).insert(Arc::clone(val));
     }
 
+    pub fn record_block_dependence(&mut self, effect: BlockDependentEffect) {
+        Arc::make_mut(&mut self.block_dependence).push(effect);
+    }
+
+    pub fn record_storage_collision(&mut self, collision: StorageCollision) {
+        Arc::make_mut(&mut self.storage_collisions).push(collision);
+    }
+
+    pub fn record_failed_call(&mut self, pc: usize, callee: AccountId, result: &BVal) {
+        Arc::make_mut(&mut self.failed_calls).push(FailedCall {
+            pc,
+            callee,
+            result: Arc::clone(result),
+        });
+    }
+
+    pub fn record_caller_checks(&mut self, slots: &[BVal]) {
+        Arc::make_mut(&mut self.caller_checks).extend(slots.iter().cloned());
+    }
+
+    /// Marks the checks against the caller or origin so far as guarding the state changing
+    /// operation at `pc`
+    pub fn record_privileged_operation(&mut self, pc: usize) {
+        if self.flags.contains(Flags::ORIGIN_CHECK) {
+            self.flags |= Flags::ORIGIN_AUTH;
+            let account = self.account;
+            Arc::make_mut(&mut self.origin_guarded_ops).push((account, pc));
+        }
+        if self.caller_checks.is_empty() {
+            return;
+        }
+        let account = self.account;
+        let slots = Arc::make_mut(&mut self.privileged_slots);
+        for slot in self.caller_checks.iter() {
+            slots.insert((account, Arc::clone(slot)));
+        }
+    }
+
+    pub fn record_mapping_read(&mut self, position: &BVal) {
+        let account = self.account;
+        Arc::make_mut(&mut self.mapping_reads).insert((account, Arc::clone(position)));
+    }
+
+    pub fn record_effect(&mut self, effect: Effect) {
+        let account = self.account;
+        Arc::make_mut(&mut self.effects).insert((account, effect));
+    }
+
+    /// Keeps the signer consistent with all previous recoveries of the same signature or its
+    /// malleable counterpart
+    pub fn record_ecrecover(&mut self, recovery: EcRecover) {
+        for earlier in self.ecrecovers.clone().iter() {
+            let same_signer = eql(&earlier.signer, &recovery.signer);
+            self.push_constraint(implies(&earlier.same_signature(&recovery), &same_signer));
+            self.push_constraint(implies(&earlier.twin_signature(&recovery), &same_signer));
+        }
+        Arc::make_mut(&mut self.ecrecovers).push(recovery);
+    }
+
+    pub fn record_storage_write(&mut self, slot: &BVal) {
+        let account = self.account;
+        Arc::make_mut(&mut self.storage_writes).push((account, Arc::clone(slot)));
+    }
+
     fn get_jump_targets_for(&mut self, val: &BVal) -> Vec<usize> {
         // check for loops
         if self.check_for_loop(val) {
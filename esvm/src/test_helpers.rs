@@ -16,17 +16,23 @@ pub fn generate_test_graph(ins: Vec<Instr>) -> SymbolicGraph {
     let attacker = env.new_attacker_account(&mut memory);
     let victim = env.new_victim_account(&mut memory, &vec![]);
     let _hijack = env.new_hijack_account(&mut memory);
-    let inital_tx = env.new_attacker_tx(&mut memory, attacker, victim);
-
-    let dasm = Disasm::new(ins);
 
     let config = CONFIG.read().unwrap().clone();
+    let inital_tx = if config.tx_origin {
+        env.new_relayed_tx(&mut memory, victim)
+    } else {
+        env.new_attacker_tx(&mut memory, attacker, victim)
+    };
+
+    let dasm = Disasm::new(ins);
 
     let initial_storage = env.get_account(&victim).storage;
+    let initial_transient = env.get_account(&victim).transient;
     let context = Context::new(
         config,
         dasm,
         initial_storage,
+        initial_transient,
         Solvers::Yice {
             count: num_cpus::get(),
             timeout: 120_000,
@@ -46,23 +52,29 @@ pub fn generate_test_graph(ins: Vec<Instr>) -> SymbolicGraph {
 }
 
 pub fn generate_test_state() -> SeState {
+    generate_test_state_with_code(&[])
+}
+
+pub fn generate_test_state_with_code(code: &[u8]) -> SeState {
     let mut env = Env::new();
     let mut memory = symbolic_memory::new_memory();
 
     let attacker = env.new_attacker_account(&mut memory);
-    let victim = env.new_victim_account(&mut memory, &vec![]);
+    let victim = env.new_victim_account(&mut memory, code);
     let _hijack = env.new_hijack_account(&mut memory);
     let inital_tx = env.new_attacker_tx(&mut memory, attacker, victim);
 
-    let dasm = Disasm::new(vec![]);
+    let dasm = Disasm::from_raw(code);
 
     let config = CONFIG.read().unwrap().clone();
 
     let initial_storage = env.get_account(&victim).storage;
+    let initial_transient = env.get_account(&victim).transient;
     let context = Context::new(
         config,
         dasm,
         initial_storage,
+        initial_transient,
         Solvers::Yice {
             count: num_cpus::get(),
             timeout: 120_000,
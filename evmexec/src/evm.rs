@@ -2,8 +2,9 @@ use log::info;
 use revm::{
     bytecode::Bytecode, database::{CacheDB, EmptyDB},
     context::tx::TxEnvBuilder,
+    context_interface::block::BlobExcessGasAndPrice,
     inspector::inspectors::TracerEip3155,
-    primitives::{Address, Bytes, TxKind, U256, HashMap},
+    primitives::{Address, B256, Bytes, TxKind, U256, HashMap},
     state::{Account, AccountInfo},
     Context,
     InspectCommitEvm,
@@ -78,6 +79,20 @@ pub struct EvmInput {
     pub receiver: Address,
     pub gas: u32,
     pub value: U256,
+    pub block: BlockInput,
+}
+
+// Block fields for the execution, unset fields keep the revm defaults
+#[derive(Debug, Clone, Default)]
+pub struct BlockInput {
+    pub number: Option<U256>,
+    pub timestamp: Option<U256>,
+    pub coinbase: Option<Address>,
+    pub prevrandao: Option<U256>,
+    pub basefee: Option<U256>,
+    pub blob_basefee: Option<U256>,
+    // Versioned hashes of the blobs the transaction carries, making it a blob transaction
+    pub blobhashes: Vec<U256>,
 }
 
 impl Evm {
@@ -96,19 +111,58 @@ impl Evm {
         let mut writer = FlushWriter::new();
 
         // Create transaction environment using TxEnvBuilder
-        let tx_env = TxEnvBuilder::new()
+        let mut tx_env = TxEnvBuilder::new()
             .caller(input.sender)
             .kind(TxKind::Call(input.receiver))
             .data(input.input_data.clone())
             .value(input.value)
             .gas_limit(input.gas as u64)
-            .nonce(nonce)
-            .build()
-            .unwrap();
+            .nonce(nonce);
+        if !input.block.blobhashes.is_empty() {
+            // the sender pays the blob fee, which has to cover the blob basefee
+            let blob_basefee = input.block.blob_basefee.unwrap_or_default();
+            let blobhashes = input
+                .block
+                .blobhashes
+                .iter()
+                .map(|hash| B256::from(hash.to_be_bytes::<32>()))
+                .collect();
+            tx_env = tx_env
+                .tx_type(Some(3))
+                .blob_hashes(blobhashes)
+                .max_fee_per_blob_gas(blob_basefee.try_into().unwrap_or_default());
+        }
+        let tx_env = tx_env.build().unwrap();
 
         // Setup the EVM from the stored CacheDB
+        let block = input.block.clone();
         let mut evm = Context::mainnet()
             .with_db(&mut self.db)
+            .modify_block_chained(|env| {
+                if let Some(number) = block.number {
+                    env.number = number.try_into().unwrap_or_default();
+                }
+                if let Some(timestamp) = block.timestamp {
+                    env.timestamp = timestamp.try_into().unwrap_or_default();
+                }
+                if let Some(coinbase) = block.coinbase {
+                    env.beneficiary = coinbase;
+                }
+                if let Some(prevrandao) = block.prevrandao {
+                    env.prevrandao = Some(B256::from(prevrandao.to_be_bytes::<32>()));
+                }
+                if let Some(basefee) = block.basefee {
+                    env.basefee = basefee.try_into().unwrap_or_default();
+                }
+                if let Some(blob_basefee) = block.blob_basefee {
+                    env.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice {
+                        excess_blob_gas: 0,
+                        blob_gasprice: blob_basefee.try_into().unwrap_or_default(),
+                    });
+                }
+            })
+            // our transactions have a gas price of zero, which a set basefee would reject
+            .modify_cfg_chained(|cfg| cfg.disable_base_fee = true)
             .build_mainnet()
             // Set an inspector to capture the trace of the execution
             .with_inspector(TracerEip3155::new(Box::new(writer.clone()))
@@ -118,6 +172,7 @@ impl Evm {
         // Execute the transaction and commit the changes back to the CacheDB
         let result = evm.inspect_tx_commit(tx_env).unwrap();
         info!("result: {:?}", result);
+        let output = if result.is_success() { result.output().cloned() } else { None };
         let trace = writer.get_buffer();
 
         // Parse the trace into a Vec<InstructionContext>
@@ -129,10 +184,35 @@ impl Evm {
             result: ExecutionResult {
                 trace: instructions,
                 new_state: State::default(),
+                output,
             },
         })
     }
 
+    // Queries the ERC-20 balance of holder through a balanceOf(address) call from the holder,
+    // executed on a copy of the state so the query is not committed
+    pub fn balance_of(&self, token: Address, holder: Address) -> Option<U256> {
+        let mut input_data = vec![0x70, 0xa0, 0x82, 0x31];
+        input_data.extend_from_slice(holder.into_word().as_slice());
+        let input = EvmInput {
+            input_data: input_data.into(),
+            sender: holder,
+            receiver: token,
+            gas: 1_000_000,
+            value: U256::ZERO,
+            block: BlockInput::default(),
+        };
+        let mut query = Evm {
+            db: self.db.clone(),
+            genesis: self.genesis.clone(),
+        };
+        let output = query.execute(input).ok()?.result.output?;
+        if output.len() < 32 {
+            return None;
+        }
+        Some(U256::from_be_slice(&output[..32]))
+    }
+
     pub fn update_state_from_genesis(&mut self) {
         // Update the CacheDB using the AccountInfo in the provided genesis
         for (addr, acc_state) in self.genesis.alloc.iter() {
@@ -195,6 +275,8 @@ pub struct EvmResult {
 pub struct ExecutionResult {
     pub trace: Vec<InstructionContext>,
     pub new_state: State,
+    // The returned data of a successful execution
+    pub output: Option<Bytes>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -260,6 +342,7 @@ mod tests {
             sender: Address::from_str("0x0dfa72de72f96cf5b127b070e90d68ec9710797c").unwrap(),
             receiver: Address::from_str("0x0ad62f08b3b9f0ecc7251befbeff80c9bb488fe9").unwrap(),
             gas: 100_000,
+            block: BlockInput::default(),
         };
         evm.execute(input).expect("Could not update evm");
 
@@ -303,6 +386,7 @@ mod tests {
             sender: Address::from_str("0x0dfa72de72f96cf5b127b070e90d68ec9710797c").unwrap(),
             receiver: Address::from_str("0x0ad62f08b3b9f0ecc7251befbeff80c9bb488fe9").unwrap(),
             gas: 100_000,
+            block: BlockInput::default(),
         };
         evm.execute(input).expect("Could not update evm");
 
@@ -75,6 +75,7 @@ pub fn parse_trace_line(line: &str) -> Option<Instruction> {
 
 struct ParsedTraceLine {
     depth: u16, // max stack depth 1024
+    pc: usize,
     instruction: Instruction,
 }
 
@@ -82,6 +83,26 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
     let output = serde_json::from_str::<Output>(line).unwrap();
     let instruction = match output.op {
         // https://github.com/trailofbits/evm-opcodes or yellowpaper
+        // only wrapping arithmetic is recorded
+        0x01..=0x03 => {
+            let mut stack = output.stack;
+            debug_assert!(stack.len() >= 2);
+            let a = stack.pop().unwrap();
+            let b = stack.pop().unwrap();
+            let (result, wraps) = match output.op {
+                0x01 => a.overflowing_add(b),
+                0x02 => a.overflowing_sub(b),
+                _ => a.overflowing_mul(b),
+            };
+            if wraps {
+                Some(Instruction::Overflow {
+                    op: output.op,
+                    result,
+                })
+            } else {
+                None
+            }
+        }
         0x54 => {
             let mut stack = output.stack;
             debug_assert!(stack.len() >= 1);
@@ -95,6 +116,29 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
             let value = stack.pop().unwrap();
             Some(Instruction::SStore { addr, value })
         }
+        0x5c => {
+            let mut stack = output.stack;
+            debug_assert!(stack.len() >= 1);
+            let addr = stack.pop().unwrap();
+            Some(Instruction::TLoad { addr })
+        }
+        0x5d => {
+            let mut stack = output.stack;
+            debug_assert!(stack.len() >= 2);
+            let addr = stack.pop().unwrap();
+            let value = stack.pop().unwrap();
+            Some(Instruction::TStore { addr, value })
+        }
+        0xa0..=0xa4 => {
+            let n = (output.op - 0xa0) as usize;
+            let mut stack = output.stack;
+            debug_assert!(stack.len() >= n + 2);
+            let offset = stack.pop().unwrap().saturating_to::<usize>();
+            let size = stack.pop().unwrap().saturating_to::<usize>();
+            let topics = (0..n).map(|_| stack.pop().unwrap()).collect();
+            let data = memory_slice(&output.memory, offset, size);
+            Some(Instruction::Log { topics, data })
+        }
         0xf1 => {
             let mut stack = output.stack;
             debug_assert!(stack.len() >= 7);
@@ -105,6 +149,11 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
             let in_size = stack.pop().unwrap();
             let out_offset = stack.pop().unwrap();
             let out_size = stack.pop().unwrap();
+            let input = memory_slice(
+                &output.memory,
+                in_offset.saturating_to::<usize>(),
+                in_size.saturating_to::<usize>(),
+            );
             Some(Instruction::Call {
                 gas,
                 receiver,
@@ -113,6 +162,7 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
                 in_size,
                 out_offset,
                 out_size,
+                input,
             })
         }
         0xf2 => {
@@ -163,6 +213,11 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
             let in_size = stack.pop().unwrap();
             let out_offset = stack.pop().unwrap();
             let out_size = stack.pop().unwrap();
+            let input = memory_slice(
+                &output.memory,
+                in_offset.saturating_to::<usize>(),
+                in_size.saturating_to::<usize>(),
+            );
             Some(Instruction::StaticCall {
                 gas,
                 receiver,
@@ -171,6 +226,7 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
                 in_size,
                 out_offset,
                 out_size,
+                input,
             })
         }
         0xff => {
@@ -180,16 +236,20 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
             Some(Instruction::Selfdestruct { receiver })
         }
         0xfd => {
-            let memory = output.memory;
-            if memory.contains(
-                "0x4e487b710000000000000000000000000000000000000000000000000000000000000001",
-            ) {
-                Some(Instruction::Revert {
-                    panic: U256::from(1),
-                })
+            let mut stack = output.stack;
+            debug_assert!(stack.len() >= 2);
+            let offset = stack.pop().unwrap().saturating_to::<usize>();
+            let size = stack.pop().unwrap().saturating_to::<usize>();
+            // revert data beyond this is never inspected
+            let data = memory_slice(&output.memory, offset, size.min(1024));
+
+            // Panic(uint256)
+            let panic = if data.len() >= 36 && data[..4] == [0x4e, 0x48, 0x7b, 0x71] {
+                Some(U256::from_be_slice(&data[4..36]))
             } else {
                 None
-            }
+            };
+            Some(Instruction::Revert { panic, data })
         }
         0xfe => Some(Instruction::Invalid {}),
         _ => None,
@@ -199,10 +259,20 @@ fn parse_trace_line_with_depth(line: &str) -> Option<ParsedTraceLine> {
     Some(ParsedTraceLine { 
         // We know the max stack depth for the EVM is 1024, so this cast is safe
         depth: depth.try_into().unwrap(),
+        pc: output.pc as usize,
         instruction
     })
 }
 
+// memory outside of the allocated region reads as zero
+fn memory_slice(memory: &str, offset: usize, size: usize) -> Vec<u8> {
+    let memory = hexdecode::decode(memory.as_bytes()).unwrap_or_default();
+    // nothing was written past the traced memory, this also keeps the huge sizes of failing LOGs from allocating
+    let start = offset.min(memory.len());
+    let end = start.saturating_add(size).min(memory.len());
+    memory[start..end].to_vec()
+}
+
 pub struct ContextParser {
     current_addr: Rc<Address>,
     current_depth: u16,
@@ -222,18 +292,17 @@ impl ContextParser {
     }
 
     pub fn parse_trace_line(&mut self, line: &str) -> Option<InstructionContext> {
-        let ParsedTraceLine { depth, instruction } = parse_trace_line_with_depth(line)?;
+        let ParsedTraceLine { depth, pc, instruction } = parse_trace_line_with_depth(line)?;
 
-        // we returned from a context
-        if depth < self.current_depth {
-            debug_assert!(depth == self.current_depth - 1); // this should allways be fullfilled
+        // we returned from one or more contexts, frames without parsed instructions are skipped
+        while depth < self.current_depth {
             let old_context = self.context.pop().unwrap(); // explicitly panic since this is undefined behaviour
             self.current_addr = old_context;
-            self.current_depth = depth;
+            self.current_depth -= 1;
         }
 
         // we jump to a new context, note neither delegatecall nor callcode change the execution
-        // context!
+        // context, they only open a new frame on the same account!
         let executed_on = match &instruction {
             Instruction::Call { receiver, .. }
             | Instruction::StaticCall { receiver, .. } => {
@@ -246,11 +315,19 @@ impl ContextParser {
 
                 old_context
             }
+            Instruction::DelegateCall { .. } | Instruction::CallCode { .. } => {
+                let current = Rc::clone(&self.current_addr);
+                self.context.push(Rc::clone(&current));
+                self.current_depth += 1;
+
+                current
+            }
             _ => Rc::clone(&self.current_addr),
         };
 
         Some(InstructionContext {
             executed_on,
+            pc,
             instruction,
         })
     }
@@ -259,6 +336,7 @@ impl ContextParser {
 #[derive(Debug, PartialEq)]
 pub struct InstructionContext {
     pub executed_on: Rc<Address>,
+    pub pc: usize,
     pub instruction: Instruction,
 }
 
@@ -271,6 +349,17 @@ pub enum Instruction {
     SLoad {
         addr: U256,
     },
+    TStore {
+        addr: U256,
+        value: U256,
+    },
+    TLoad {
+        addr: U256,
+    },
+    Log {
+        topics: Vec<U256>,
+        data: Vec<u8>,
+    },
     Call {
         gas: U256,
         receiver: Address,
@@ -279,6 +368,8 @@ pub enum Instruction {
         in_size: U256,
         out_offset: U256,
         out_size: U256,
+        /// The input data, as far as it is within the traced memory
+        input: Vec<u8>,
     },
     StaticCall {
         gas: U256,
@@ -288,6 +379,8 @@ pub enum Instruction {
         in_size: U256,
         out_offset: U256,
         out_size: U256,
+        /// The input data, as far as it is within the traced memory
+        input: Vec<u8>,
     },
     CallCode {
         gas: U256,
@@ -310,7 +403,12 @@ pub enum Instruction {
         receiver: Address,
     },
     Revert {
-        panic: U256,
+        panic: Option<U256>,
+        data: Vec<u8>,
+    },
+    Overflow {
+        op: u8,
+        result: U256,
     },
     Invalid {},
 }
@@ -328,6 +426,68 @@ mod tests {
         assert_eq!(correct, parse_trace_line(SAMPLE_WRITE).unwrap());
     }
 
+    #[test]
+    fn transient_storage_write_parsing() {
+        let correct = Instruction::TStore {
+            addr: U256::from(1),
+            value: U256::from(0x2a),
+        };
+        assert_eq!(correct, parse_trace_line(SAMPLE_TRANSIENT_WRITE).unwrap());
+    }
+
+    #[test]
+    fn log_parsing() {
+        let correct = Instruction::Log {
+            topics: vec![
+                U256::from_str_radix(
+                    "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    16,
+                )
+                .unwrap(),
+                U256::from(0xaa),
+                U256::from(0xbb),
+            ],
+            data: vec![0, 0, 0, 0x2a],
+        };
+        assert_eq!(correct, parse_trace_line(SAMPLE_LOG).unwrap());
+    }
+
+    #[test]
+    fn log_parsing_clamps_size() {
+        let parsed = parse_trace_line(SAMPLE_HUGE_LOG).unwrap();
+        assert_eq!(
+            Instruction::Log {
+                topics: vec![],
+                data: vec![0, 0x2a],
+            },
+            parsed
+        );
+    }
+
+    #[test]
+    fn panic_parsing() {
+        let mut data = vec![0x4e, 0x48, 0x7b, 0x71];
+        data.resize(35, 0);
+        data.push(0x11);
+        let correct = Instruction::Revert {
+            panic: Some(U256::from(0x11)),
+            data,
+        };
+        assert_eq!(correct, parse_trace_line(SAMPLE_PANIC).unwrap());
+    }
+
+    #[test]
+    fn overflow_parsing() {
+        assert_eq!(
+            Instruction::Overflow {
+                op: 0x02,
+                result: U256::MAX,
+            },
+            parse_trace_line(SAMPLE_OVERFLOW).unwrap()
+        );
+        assert_eq!(None, parse_trace_line(SAMPLE_NO_OVERFLOW));
+    }
+
     #[test]
     fn parsing_with_context_switch() {
         let mut parser = ContextParser::new("0x0dfa72de72f96cf5b127b070e90d68ec9710797c".parse::<Address>().unwrap());
@@ -341,6 +501,7 @@ mod tests {
         let correct = vec![
             InstructionContext {
                 executed_on: Rc::new("0x0DFA72DE72F96CF5B127B070E90D68EC9710797C".parse::<Address>().unwrap()),
+                pc: 301,
                 instruction: Instruction::Call {
                     gas: U256::from(3998580),
                     receiver: "0x86C249452EE469D839942E05B8492DBB9F9C70AC".parse::<Address>().unwrap(),
@@ -349,10 +510,15 @@ mod tests {
                     in_size: U256::from(36),
                     out_offset: U256::from(128),
                     out_size: U256::from(0),
+                    input: hexdecode::decode(
+                        b"338ccd780000000000000000000000000dfa72de72f96cf5b127b070e90d68ec9710797c",
+                    )
+                    .unwrap(),
                 },
             },
             InstructionContext {
                 executed_on: Rc::new("0x86C249452EE469D839942E05B8492DBB9F9C70AC".parse::<Address>().unwrap()),
+                pc: 217,
                 instruction: Instruction::Call {
                     gas: U256::from(0),
                     receiver: "0x0DFA72DE72F96CF5B127B070E90D68EC9710797C".parse::<Address>().unwrap(),
@@ -361,6 +527,7 @@ mod tests {
                     in_size: U256::from(0),
                     out_offset: U256::from(96),
                     out_size: U256::from(0),
+                    input: vec![],
                 },
             },
         ];
@@ -369,8 +536,42 @@ mod tests {
         assert!(correct.iter().all(|instruction| parsed.contains(instruction)))
     }
 
+    #[test]
+    fn parsing_with_delegate_call() {
+        let proxy = "0x0dfa72de72f96cf5b127b070e90d68ec9710797c".parse::<Address>().unwrap();
+        let callee = "0x86c249452ee469d839942e05b8492dbb9f9c70ac".parse::<Address>().unwrap();
+        let mut parser = ContextParser::new(proxy);
+        let executed_on: Vec<Address> = SAMPLE_TRACE_WITH_DELEGATE_CALL
+            .lines()
+            .filter_map(|line| parser.parse_trace_line(line))
+            .map(|ins| *ins.executed_on)
+            .collect();
+
+        // the delegated code writes to the proxy, also after its own call returned
+        assert_eq!(vec![proxy, proxy, proxy, callee, proxy, proxy], executed_on);
+    }
+
+    const SAMPLE_OVERFLOW: &'static str = r#"{"pc":12,"op":2,"gas":"0x3d0792","gasCost":"0x3","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x2","0x1"],"depth":1,"opName":"SUB","error":""}"#;
+
+    const SAMPLE_NO_OVERFLOW: &'static str = r#"{"pc":12,"op":1,"gas":"0x3d0792","gasCost":"0x3","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x2","0x1"],"depth":1,"opName":"ADD","error":""}"#;
+
+    const SAMPLE_PANIC: &'static str = r#"{"pc":77,"op":253,"gas":"0x3d0792","gasCost":"0x0","refund":"0x0","memory":"0x4e487b710000000000000000000000000000000000000000000000000000000000000011","memSize":"0x40","stack":["0x24","0x0"],"depth":1,"opName":"REVERT","error":""}"#;
+
+    const SAMPLE_LOG: &'static str = r#"{"pc":42,"op":163,"gas":"0x3d0792","gasCost":"0x5dc","refund":"0x0","memory":"0x0000002a","memSize":"0x20","stack":["0xbb","0xaa","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x4","0x0"],"depth":1,"opName":"LOG3","error":""}"#;
+
+    const SAMPLE_HUGE_LOG: &'static str = r#"{"pc":42,"op":160,"gas":"0x3d0792","gasCost":"0x177","refund":"0x0","memory":"0x0000002a","memSize":"0x20","stack":["0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff","0x2"],"depth":1,"opName":"LOG0","error":""}"#;
+
+    const SAMPLE_TRANSIENT_WRITE: &'static str = r#"{"pc":7,"op":93,"gas":"0x3d0792","gasCost":"0x64","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x2a","0x1"],"depth":1,"opName":"TSTORE","error":""}"#;
+
     const SAMPLE_WRITE: &'static str = r#"{"pc":356,"op":85,"gas":"0x3d0792","gasCost":"0x1388","refund":"0x0","memory":"0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000080","memSize":"0x60","stack":["0xe9ca826c","0x7e","0xdfa72de72f96cf5b127b070e90d68ec9710797c","0x0"],"depth":1,"opName":"SSTORE","error":""}"#;
 
+    const SAMPLE_TRACE_WITH_DELEGATE_CALL: &'static str = r#"{"pc":10,"op":244,"gas":"0x3d0900","gasCost":"0x0","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x0","0x0","0x0","0x0","0x0","0xabcdef","0x100000"],"depth":1,"opName":"DELEGATECALL","error":""}
+    {"pc":5,"op":85,"gas":"0x3d0792","gasCost":"0x5654","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x1","0x0"],"depth":2,"opName":"SSTORE","error":""}
+    {"pc":7,"op":241,"gas":"0x3d0792","gasCost":"0x0","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x0","0x0","0x0","0x0","0x0","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x10000"],"depth":2,"opName":"CALL","error":""}
+    {"pc":0,"op":85,"gas":"0x3d0792","gasCost":"0x5654","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x1","0x1"],"depth":3,"opName":"SSTORE","error":""}
+    {"pc":9,"op":85,"gas":"0x3d0792","gasCost":"0x5654","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x1","0x2"],"depth":2,"opName":"SSTORE","error":""}
+    {"pc":12,"op":85,"gas":"0x3d0792","gasCost":"0x5654","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x1","0x3"],"depth":1,"opName":"SSTORE","error":""}"#;
+
     const SAMPLE_TRACE_WITH_CONTEXT_SWITCH: &'static str = r#"{"pc":0,"op":96,"gas":"0x3d0900","gasCost":"0x3","refund":"0x0","memory":"0x","memSize":"0x0","stack":[],"depth":1,"opName":"PUSH1","error":""}
     {"pc":2,"op":96,"gas":"0x3d08fd","gasCost":"0x3","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x80"],"depth":1,"opName":"PUSH1","error":""}
     {"pc":4,"op":82,"gas":"0x3d08fa","gasCost":"0xc","refund":"0x0","memory":"0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","memSize":"0x60","stack":["0x80","0x40"],"depth":1,"opName":"MSTORE","error":""}
@@ -464,7 +665,7 @@ mod tests {
     {"pc":298,"op":91,"gas":"0x3d0379","gasCost":"0x1","refund":"0x0","memory":"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000338ccd780000000000000000000000000dfa72de72f96cf5b127b070e90d68ec9710797c00000000000000000000000000000000000000000000000000000000","memSize":"0xC0","stack":["0x7c52e325","0x7e","0xdfa72de72f96cf5b127b070e90d68ec9710797c","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x338ccd78","0xa4","0x0","0x80","0x24","0x80","0x0","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x0"],"depth":1,"opName":"JUMPDEST","error":""}
     {"pc":299,"op":80,"gas":"0x3d0378","gasCost":"0x2","refund":"0x0","memory":"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000338ccd780000000000000000000000000dfa72de72f96cf5b127b070e90d68ec9710797c00000000000000000000000000000000000000000000000000000000","memSize":"0xC0","stack":["0x7c52e325","0x7e","0xdfa72de72f96cf5b127b070e90d68ec9710797c","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x338ccd78","0xa4","0x0","0x80","0x24","0x80","0x0","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x0"],"depth":1,"opName":"POP","error":""}
     {"pc":300,"op":90,"gas":"0x3d0376","gasCost":"0x2","refund":"0x0","memory":"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000338ccd780000000000000000000000000dfa72de72f96cf5b127b070e90d68ec9710797c00000000000000000000000000000000000000000000000000000000","memSize":"0xC0","stack":["0x7c52e325","0x7e","0xdfa72de72f96cf5b127b070e90d68ec9710797c","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x338ccd78","0xa4","0x0","0x80","0x24","0x80","0x0","0x86c249452ee469d839942e05b8492dbb9f9c70ac"],"depth":1,"opName":"GAS","error":""}
-    {"pc":301,"op":241,"gas":"0x3d0374","gasCost":"0x3c0f72","refund":"0x0","memory":"0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000338ccd780000000000000000000000000dfa72de72f96cf5b127b070e90d68ec9710797c00000000000000000000000000000000000000000000000000000000","memSize":"0xC0","stack":["0x7c52e325","0x7e","0xdfa72de72f96cf5b127b070e90d68ec9710797c","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x338ccd78","0xa4","0x0","0x80","0x24","0x80","0x0","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x3d0374"],"depth":1,"opName":"CALL","error":""}
+    {"pc":301,"op":241,"gas":"0x3d0374","gasCost":"0x3c0f72","refund":"0x0","memory":"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000338ccd780000000000000000000000000dfa72de72f96cf5b127b070e90d68ec9710797c00000000000000000000000000000000000000000000000000000000","memSize":"0xC0","stack":["0x7c52e325","0x7e","0xdfa72de72f96cf5b127b070e90d68ec9710797c","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x338ccd78","0xa4","0x0","0x80","0x24","0x80","0x0","0x86c249452ee469d839942e05b8492dbb9f9c70ac","0x3d0374"],"depth":1,"opName":"CALL","error":""}
     {"pc":0,"op":96,"gas":"0x3c0cb6","gasCost":"0x3","refund":"0x0","memory":"0x","memSize":"0x0","stack":[],"depth":2,"opName":"PUSH1","error":""}
     {"pc":2,"op":96,"gas":"0x3c0cb3","gasCost":"0x3","refund":"0x0","memory":"0x","memSize":"0x0","stack":["0x60"],"depth":2,"opName":"PUSH1","error":""}
     {"pc":4,"op":82,"gas":"0x3c0cb0","gasCost":"0xc","refund":"0x0","memory":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","memSize":"0x60","stack":["0x60","0x40"],"depth":2,"opName":"MSTORE","error":""}